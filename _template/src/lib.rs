@@ -1,9 +1,33 @@
-pub fn part1(_input: &str) -> String {
-    "Hello".to_string()
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct DayNN;
+
+impl Solution for DayNN {
+    const DAY: u32 = NN;
+    type Answer1 = String;
+    type Answer2 = String;
+    const PART1: &'static [Variant<String>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<String>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+pub fn part1(input: &str) -> String {
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(_input: &str) -> Result<String, AocError> {
+    Ok("Hello".to_string())
+}
+
+pub fn part2(input: &str) -> String {
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
 }
 
-pub fn part2(_input: &str) -> String {
-    "World".to_string()
+pub fn try_part2(_input: &str) -> Result<String, AocError> {
+    Ok("World".to_string())
 }
 
 #[test]
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+//
+// The known answers for each day and part, from answers.toml at the
+// top of the workspace.  The file looks like:
+//
+//      [day01]
+//      part1 = 2375403
+//      part2 = 23082277
+//
+// Answers may be integers or strings; they're compared against the
+// `Display` form of each implementation's result.
+//
+pub struct Answers {
+    answers: HashMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("unable to read {}: {err}", path.display()))?;
+        Answers::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, Box<dyn std::error::Error>> {
+        let table: toml::Table = text.parse()?;
+        let mut answers = HashMap::new();
+        for (day_key, parts) in &table {
+            let day = parse_key(day_key, "day")?;
+            let parts = parts.as_table().ok_or_else(|| format!("[{day_key}] should be a table"))?;
+            for (part_key, answer) in parts {
+                let part = parse_key(part_key, "part")?;
+                let answer = match answer {
+                    toml::Value::String(s) => s.clone(),
+                    toml::Value::Integer(n) => n.to_string(),
+                    _ => return Err(format!("{day_key}.{part_key} should be an integer or string").into()),
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+fn parse_key(key: &str, prefix: &str) -> Result<u32, String> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("expected a key like {prefix}1, found {key:?}"))
+}
+
+#[test]
+fn test_parse() {
+    let answers = Answers::parse("\
+[day01]
+part1 = 11
+part2 = 31
+
+[day18]
+part2 = \"6,1\"
+").unwrap();
+    assert_eq!(answers.get(1, 1), Some("11"));
+    assert_eq!(answers.get(1, 2), Some("31"));
+    assert_eq!(answers.get(18, 1), None);
+    assert_eq!(answers.get(18, 2), Some("6,1"));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(Answers::parse("[dayone]\npart1 = 1\n").is_err());
+    assert!(Answers::parse("[day01]\npart3x = 1\n").is_err());
+    assert!(Answers::parse("[day01]\npart1 = 1.5\n").is_err());
+    assert!(Answers::parse("day01 = 5\n").is_err());
+}
@@ -0,0 +1,381 @@
+use std::io::Read;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc_utils::{compare_variants, generate, load_input, VariantId};
+use clap::{Parser, Subcommand};
+
+mod answers;
+mod registry;
+use answers::Answers;
+use registry::REGISTRY;
+
+#[derive(Parser)]
+#[command(about = "Run the Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day's puzzle
+    Run {
+        /// Which day (1-25)
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Which part (1 or 2); both if omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+
+        /// Which implementation; the default if omitted
+        #[arg(short, long)]
+        variant: Option<String>,
+
+        /// Input file, or "-" for stdin; found by `aoc_utils::input_path` if omitted
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+
+    /// List every implementation of every part
+    List {
+        /// Only list this day
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: Option<u32>,
+    },
+
+    /// Check every implementation against the recorded answers
+    Verify {
+        /// Only verify this day
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: Option<u32>,
+
+        /// The recorded answers; answers.toml in the workspace if omitted
+        #[arg(short, long)]
+        answers: Option<PathBuf>,
+    },
+
+    /// Check that every implementation of a part gives the same answer
+    Compare {
+        /// Only compare this day
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: Option<u32>,
+
+        /// Input file, or "-" for stdin (requires a day); each day's own input if omitted
+        #[arg(short, long, requires = "day", conflicts_with = "generate")]
+        input: Option<PathBuf>,
+
+        /// Use randomly generated input instead of each day's own
+        #[arg(short, long)]
+        generate: bool,
+
+        /// Seed for the generated input
+        #[arg(long, default_value_t = 0, requires = "generate")]
+        seed: u64,
+
+        /// Size of the generated input; about the size of the real input if omitted
+        #[arg(long, requires = "generate")]
+        size: Option<usize>,
+    },
+
+    /// Time every implementation of a day on generated inputs of increasing size
+    Scale {
+        /// Which day (1-25)
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Which part (1 or 2); both if omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+
+        /// Seed for the generated inputs
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Comma separated input sizes; from 1/8 to 2 times the real input's size if omitted
+        #[arg(long, value_delimiter = ',')]
+        sizes: Vec<usize>,
+
+        /// Stop timing an implementation once it takes longer than this many seconds
+        #[arg(long, default_value_t = 10.0)]
+        limit: f64,
+    },
+
+    /// Print a randomly generated input for one day
+    Generate {
+        /// Which day (1-25)
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Seed for the random numbers; the same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big an input (what that means varies by day); about the size of the real input if omitted
+        #[arg(long)]
+        size: Option<usize>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run { day, part, variant, input } => run(day, part, variant.as_deref(), input),
+        Command::List { day } => list(day),
+        Command::Verify { day, answers } => verify(day, answers),
+        Command::Compare { day, input, generate: false, .. } => compare(day, |day| read_input(day, input.clone())),
+        Command::Compare { day, generate: true, seed, size, .. } => compare(day, |day| Ok(generated_input(day, seed, size))),
+        Command::Scale { day, part, seed, sizes, limit } => scale(day, part, seed, sizes, Duration::from_secs_f64(limit)),
+        Command::Generate { day, seed, size } => {
+            print!("{}", generated_input(day, seed, size));
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+fn run(day: u32, part: Option<u32>, variant: Option<&str>, input: Option<PathBuf>) -> ExitCode {
+    let input = match read_input(day, input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Unable to read input for day {day}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    for part in parts {
+        let Some(id) = REGISTRY.find(day, part, variant) else {
+            let known = REGISTRY.day(day).map(|d| d.variants(part)).unwrap_or_default();
+            eprintln!("Day {day} part {part} has no variant {:?} (known: {})",
+                variant.unwrap_or_default(), known.join(", "));
+            return ExitCode::FAILURE;
+        };
+
+        let start = Instant::now();
+        let answer = REGISTRY.solve(id, &input).expect("registered variant");
+        let elapsed = start.elapsed();
+        match answer {
+            Ok(answer) => println!("Part {part}: {answer}  [{} {elapsed:.3?}]", id.variant),
+            Err(err) => {
+                eprintln!("Part {part}: invalid input: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn list(day: Option<u32>) -> ExitCode {
+    for id in REGISTRY.variants().filter(|id| day.is_none_or(|day| id.day == day)) {
+        println!("day {:2}  part {}  {}", id.day, id.part, id.variant);
+    }
+    ExitCode::SUCCESS
+}
+
+fn verify(day: Option<u32>, answers: Option<PathBuf>) -> ExitCode {
+    let path = answers.unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml"));
+    let answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("Unable to load answers: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+    println!("{:>3} {:>4}  {:<14} {:<7} {:>10}", "day", "part", "variant", "result", "time");
+    for solution in REGISTRY.days().filter(|d| day.is_none_or(|day| d.day() == day)) {
+        let input = load_input(solution.day());
+        for part in [1, 2] {
+            for variant in solution.variants(part) {
+                let id = REGISTRY.find(solution.day(), part, Some(variant)).expect("registered variant");
+                let check = match &input {
+                    Ok(input) => check(id, input, answers.get(id.day, part)),
+                    Err(_) => Check { result: "missing", elapsed: None, detail: "no input".to_string() },
+                };
+                if check.result == "FAIL" {
+                    failures += 1;
+                }
+                let elapsed = check.elapsed.map(|e| format!("{e:.3?}")).unwrap_or_default();
+                println!("{:>3} {:>4}  {:<14} {:<7} {:>10}  {}",
+                    id.day, id.part, id.variant, check.result, elapsed, check.detail);
+            }
+        }
+    }
+
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{failures} implementation(s) gave the wrong answer");
+        ExitCode::FAILURE
+    }
+}
+
+struct Check {
+    result: &'static str,
+    elapsed: Option<Duration>,
+    detail: String,
+}
+
+fn check(id: VariantId, input: &str, expected: Option<&str>) -> Check {
+    let start = Instant::now();
+    let answer = catch_unwind(AssertUnwindSafe(|| REGISTRY.solve(id, input).expect("registered variant")));
+    let elapsed = Some(start.elapsed());
+    match (answer, expected) {
+        (Err(_), _) => Check { result: "FAIL", elapsed, detail: "panicked".to_string() },
+        (Ok(Err(err)), _) => Check { result: "FAIL", elapsed, detail: format!("invalid input: {err}") },
+        (Ok(Ok(answer)), None) => Check { result: "missing", elapsed, detail: format!("no recorded answer; got {answer}") },
+        (Ok(Ok(answer)), Some(expected)) if answer == expected => Check { result: "pass", elapsed, detail: String::new() },
+        (Ok(Ok(answer)), Some(expected)) => Check { result: "FAIL", elapsed, detail: format!("expected {expected}, got {answer}") },
+    }
+}
+
+fn compare(day: Option<u32>, input: impl Fn(u32) -> Result<String, Box<dyn std::error::Error>>) -> ExitCode {
+    let mut disagreements = 0;
+    for solution in REGISTRY.days().filter(|d| day.is_none_or(|day| d.day() == day)) {
+        // When comparing every day, only bother with parts that have
+        // more than one implementation.
+        let parts: Vec<u32> = [1, 2].into_iter()
+            .filter(|&part| day.is_some() || solution.variants(part).len() > 1)
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        let input = match input(solution.day()) {
+            Ok(input) => input,
+            Err(err) if day.is_some() => {
+                eprintln!("Unable to read input for day {}: {err}", solution.day());
+                return ExitCode::FAILURE;
+            }
+            Err(_) => {
+                println!("day {:2}: skipped (no input)", solution.day());
+                continue;
+            }
+        };
+        for part in parts {
+            let count = solution.variants(part).len();
+            match compare_variants(solution, part, &input) {
+                Ok(Some(outcome)) => println!("day {:2} part {part}: {count} variant(s) agree: {outcome}", solution.day()),
+                Ok(None) => {}
+                Err(disagreement) => {
+                    disagreements += 1;
+                    print!("{disagreement}");
+                }
+            }
+        }
+    }
+
+    if disagreements == 0 {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{disagreements} part(s) have variants that disagree");
+        ExitCode::FAILURE
+    }
+}
+
+//
+// Prints a table of size vs. time, with a column per implementation:
+//
+//      day 9 part 2 (digits in the disk map)
+//         size  heaps       default
+//         2499  61.503µs    358.320µs
+//         4999  114.970µs   1.580ms
+//
+// An implementation that takes longer than the limit isn't run on the
+// larger sizes.
+//
+fn scale(day: u32, part: Option<u32>, seed: u64, sizes: Vec<usize>, limit: Duration) -> ExitCode {
+    let generator = generate::generator(day).expect("every day has a generator");
+    let mut sizes = match sizes.is_empty() {
+        true => [8, 4, 2].map(|n| (generator.default_size / n).max(1)).into_iter()
+            .chain([generator.default_size, 2 * generator.default_size])
+            .collect(),
+        false => sizes,
+    };
+    sizes.dedup();
+
+    let solution = REGISTRY.day(day).expect("registered day");
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    for part in parts {
+        let variants = solution.variants(part);
+        let width = variants.iter().map(|variant| variant.len()).max().unwrap_or(0).max(10);
+        println!("day {day} part {part} ({})", generator.size);
+        let header: String = variants.iter().map(|variant| format!("  {variant:<width$}")).collect();
+        println!("{:>7}{}", "size", header.trim_end());
+
+        let mut too_slow = vec![false; variants.len()];
+        for &size in &sizes {
+            let input = generator.generate(seed, size);
+            let mut row = format!("{size:>7}");
+            for (variant, too_slow) in variants.iter().zip(too_slow.iter_mut()) {
+                let cell = if *too_slow {
+                    "-".to_string()
+                } else {
+                    let id = REGISTRY.find(day, part, Some(variant)).expect("registered variant");
+                    let start = Instant::now();
+                    let answer = catch_unwind(AssertUnwindSafe(|| REGISTRY.solve(id, &input).expect("registered variant")));
+                    let elapsed = start.elapsed();
+                    *too_slow = elapsed > limit;
+                    match answer {
+                        Ok(Ok(_)) => format!("{elapsed:.3?}"),
+                        Ok(Err(_)) => "invalid".to_string(),
+                        Err(_) => "panicked".to_string(),
+                    }
+                };
+                row += &format!("  {cell:<width$}");
+            }
+            println!("{}", row.trim_end());
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn generated_input(day: u32, seed: u64, size: Option<usize>) -> String {
+    let generator = generate::generator(day).expect("every day has a generator");
+    generator.generate(seed, size.unwrap_or(generator.default_size))
+}
+
+fn read_input(day: u32, path: Option<PathBuf>) -> Result<String, Box<dyn std::error::Error>> {
+    match path {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => Ok(std::fs::read_to_string(path)?),
+        None => Ok(load_input(day)?),
+    }
+}
+
+//
+// Every variant of every part should agree on generated inputs, and
+// the generators should only produce inputs the solutions accept.
+//
+#[test]
+fn test_generated_inputs() {
+    use aoc_utils::Outcome;
+    for solution in REGISTRY.days() {
+        let generator = generate::generator(solution.day()).expect("every day has a generator");
+        for seed in 0..3 {
+            let input = generator.generate(seed, generator.small_size);
+            for part in [1, 2] {
+                match compare_variants(solution, part, &input) {
+                    Ok(Some(Outcome::Answer(_))) | Ok(None) => {}
+                    Ok(Some(outcome)) => panic!("day {} part {part} seed {seed}: {outcome}", solution.day()),
+                    Err(disagreement) => panic!("seed {seed}: {disagreement}"),
+                }
+            }
+        }
+    }
+}
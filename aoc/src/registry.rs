@@ -0,0 +1,29 @@
+use aoc_utils::{erase, Registry};
+
+pub static REGISTRY: Registry = Registry::new(&[
+    &erase::<day01::Day01>(),
+    &erase::<day02::Day02>(),
+    &erase::<day03::Day03>(),
+    &erase::<day04::Day04>(),
+    &erase::<day05::Day05>(),
+    &erase::<day06::Day06>(),
+    &erase::<day07::Day07>(),
+    &erase::<day08::Day08>(),
+    &erase::<day09::Day09>(),
+    &erase::<day10::Day10>(),
+    &erase::<day11::Day11>(),
+    &erase::<day12::Day12>(),
+    &erase::<day13::Day13>(),
+    &erase::<day14::Day14>(),
+    &erase::<day15::Day15>(),
+    &erase::<day16::Day16>(),
+    &erase::<day17::Day17>(),
+    &erase::<day18::Day18>(),
+    &erase::<day19::Day19>(),
+    &erase::<day20::Day20>(),
+    &erase::<day21::Day21>(),
+    &erase::<day22::Day22>(),
+    &erase::<day23::Day23>(),
+    &erase::<day24::Day24>(),
+    &erase::<day25::Day25>(),
+]);
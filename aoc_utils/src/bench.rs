@@ -0,0 +1,26 @@
+//
+// Shared setup for the `benches`.  Benchmarks that need the real input
+// go in a module named `full_input`, and are skipped when it's missing.
+// The rest use generated inputs, which always exist; they all use the
+// same seed, so that runs can be compared.
+//
+use crate::generate::generate;
+use crate::input_path;
+
+pub const SEED: u64 = 1;
+
+pub fn main(day: u32) {
+    let mut divan = divan::Divan::from_args();
+    if let Err(err) = input_path(day) {
+        eprintln!("Skipping full input benchmarks: {err}");
+        divan = divan.skip_regex("full_input");
+    }
+    divan.main();
+}
+
+//
+// The day's generated input of the given size.
+//
+pub fn generated(day: u32, size: usize) -> String {
+    generate(day, SEED, size).unwrap_or_else(|| panic!("day {day} has no generator"))
+}
@@ -0,0 +1,168 @@
+use std::fmt::Display;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::{erase, DynSolution, Solution};
+
+//
+// Differential testing: run every implementation of a part on the same
+// input, and complain if any of them disagree.  This lets a new variant
+// be checked against the existing ones without knowing the answer.
+//
+
+//
+// What one implementation did with the input.
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    Invalid(String),
+    Panicked(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{answer}"),
+            Outcome::Invalid(err) => write!(f, "invalid input: {err}"),
+            Outcome::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub day: u32,
+    pub part: u32,
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+//
+// Shows every variant's outcome, marking the ones that differ from
+// the default (first) variant:
+//
+//      day 20 part 2: variants disagree
+//          v2  1011325
+//        ! v1  1011324
+//
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "day {} part {}: variants disagree", self.day, self.part)?;
+        let width = self.outcomes.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let expected = &self.outcomes[0].1;
+        for (name, outcome) in &self.outcomes {
+            let marker = if outcome == expected { ' ' } else { '!' };
+            writeln!(f, "  {marker} {name:<width$}  {outcome}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Disagreement {}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "(unknown panic)".to_string()
+    }
+}
+
+//
+// Run one variant, catching any panic.  Returns None if there is no
+// such variant.
+//
+pub fn run_variant(solution: &dyn DynSolution, part: u32, variant: &str, input: &str) -> Option<Outcome> {
+    let result = catch_unwind(AssertUnwindSafe(|| solution.solve(part, variant, input)));
+    Some(match result {
+        Ok(Some(Ok(answer))) => Outcome::Answer(answer),
+        Ok(Some(Err(err))) => Outcome::Invalid(err.to_string()),
+        Ok(None) => return None,
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    })
+}
+
+//
+// Run every variant of the given part.  If they all agree, return their
+// common outcome (or None if the part has no variants).
+//
+pub fn compare_variants(solution: &dyn DynSolution, part: u32, input: &str) -> Result<Option<Outcome>, Disagreement> {
+    let outcomes: Vec<_> = solution.variants(part).into_iter()
+        .map(|name| (name, run_variant(solution, part, name, input).expect("registered variant")))
+        .collect();
+    let Some((_, first)) = outcomes.first() else {
+        return Ok(None);
+    };
+    if outcomes.iter().all(|(_, outcome)| outcome == first) {
+        Ok(Some(first.clone()))
+    } else {
+        Err(Disagreement { day: solution.day(), part, outcomes })
+    }
+}
+
+//
+// For tests: panic (showing every variant's answer) unless all of the
+// variants of both parts agree.  Returns the answers they agree on.
+//
+pub fn assert_variants_agree<S: Solution + Sync>(input: &str) -> (Option<Outcome>, Option<Outcome>) {
+    let solution = erase::<S>();
+    let agree = |part| compare_variants(&solution, part, input).unwrap_or_else(|disagreement| panic!("{disagreement}"));
+    (agree(1), agree(2))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AocError, Variant};
+
+    struct Example;
+    impl Solution for Example {
+        const DAY: u32 = 3;
+        type Answer1 = usize;
+        type Answer2 = usize;
+        const PART1: &'static [Variant<usize>] = &[
+            Variant::new("len", |input| input.len()),
+            Variant::new("lines", |input| input.lines().map(|line| line.len() + 1).sum::<usize>() - 1),
+        ];
+        const PART2: &'static [Variant<usize>] = &[
+            Variant::new("len", |input| input.len()),
+            Variant::new("chars", |input| input.chars().count()),
+            Variant::fallible("ascii", |input| match input.is_ascii() {
+                true => Ok(input.len()),
+                false => Err(AocError::new("not ASCII")),
+            }),
+            Variant::new("panics", |input| input.find('!').map(|_| input.len()).expect("no bang")),
+        ];
+    }
+
+    #[test]
+    fn test_agree() {
+        let (part1, part2) = assert_variants_agree::<Example>("h\ni!");
+        assert_eq!(part1, Some(Outcome::Answer("4".to_string())));
+        assert_eq!(part2, Some(Outcome::Answer("4".to_string())));
+    }
+
+    #[test]
+    fn test_disagree() {
+        let solution = erase::<Example>();
+        assert!(compare_variants(&solution, 1, "é").is_ok());
+        let disagreement = compare_variants(&solution, 2, "é").unwrap_err();
+        assert_eq!(disagreement.outcomes[1], ("chars", Outcome::Answer("1".to_string())));
+        assert_eq!(disagreement.outcomes[2], ("ascii", Outcome::Invalid("not ASCII".to_string())));
+        assert_eq!(disagreement.outcomes[3], ("panics", Outcome::Panicked("no bang".to_string())));
+        assert_eq!(disagreement.to_string(), "\
+day 3 part 2: variants disagree
+    len     2
+  ! chars   1
+  ! ascii   invalid input: not ASCII
+  ! panics  panicked: no bang
+");
+    }
+
+    #[test]
+    #[should_panic(expected = "day 3 part 2: variants disagree")]
+    fn test_assert_disagree() {
+        assert_variants_agree::<Example>("hi");
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::Display;
+
+//
+// Something wrong with a puzzle input.  When we know where in the input
+// the problem is, `line` and `column` say where (both starting at 1).
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl AocError {
+    pub fn new(message: impl Into<String>) -> Self {
+        AocError { message: message.into(), line: None, column: None }
+    }
+
+    //
+    // An error on the given (1-based) line of the input.
+    //
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        AocError { message: message.into(), line: Some(line), column: None }
+    }
+
+    //
+    // An error at a (row, column) position in a grid.  Like `Grid`, the
+    // position starts at (0, 0).
+    //
+    pub fn at_cell((row, col): crate::grid::Pos, message: impl Into<String>) -> Self {
+        AocError { message: message.into(), line: Some(row + 1), column: Some(col + 1) }
+    }
+
+    //
+    // An error at byte `offset` in `input`.
+    //
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        AocError { message: message.into(), line: Some(line), column: Some(column) }
+    }
+
+    //
+    // An error where `remaining` (a suffix of `input`) begins.  This is
+    // what parsers generally have in hand when they fail.
+    //
+    pub fn at(input: &str, remaining: &str, message: impl Into<String>) -> Self {
+        AocError::at_offset(input, input.len().saturating_sub(remaining.len()), message)
+    }
+
+    //
+    // Errors found while looking at a single line don't know which line
+    // they came from; this fills it in.
+    //
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: {}", self.message),
+            (Some(line), None) => write!(f, "line {line}: {}", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+//
+// Attach a line number to any error that converts to an AocError, e.g.
+// `word.parse::<u32>().on_line(7)?`.
+//
+pub trait LineContext<T> {
+    fn on_line(self, line: usize) -> Result<T, AocError>;
+}
+
+impl<T, E: Into<AocError>> LineContext<T> for Result<T, E> {
+    fn on_line(self, line: usize) -> Result<T, AocError> {
+        self.map_err(|err| err.into().on_line(line))
+    }
+}
+
+impl From<std::num::ParseIntError> for AocError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        AocError::new(format!("invalid number: {err}"))
+    }
+}
+
+impl From<crate::grid::RaggedGridError> for AocError {
+    fn from(err: crate::grid::RaggedGridError) -> Self {
+        AocError::at_line(err.line, format!("expected {} columns, found {}", err.expected, err.found))
+    }
+}
+
+impl From<crate::grid::ParseGridError> for AocError {
+    fn from(err: crate::grid::ParseGridError) -> Self {
+        match err {
+            crate::grid::ParseGridError::Ragged(err) => err.into(),
+            crate::grid::ParseGridError::NotAscii { line, col, ch } =>
+                AocError::at_cell((line - 1, col - 1), format!("unexpected {ch:?}")),
+        }
+    }
+}
+
+impl From<crate::geometry::InvalidDirection> for AocError {
+    fn from(err: crate::geometry::InvalidDirection) -> Self {
+        AocError::new(err.to_string())
+    }
+}
+
+//
+// Converting nom's errors.  A nom error only knows the remaining input
+// where parsing failed, so `located_in` needs the original input to
+// figure out the line and column.
+//
+pub trait NomResultExt<'a, T> {
+    fn located_in(self, input: &'a str) -> Result<(&'a str, T), AocError>;
+}
+
+#[allow(unused_macros)]
+macro_rules! nom_conversions {
+    ($nom:ident) => {
+        fn nom_message(code: $nom::error::ErrorKind) -> String {
+            format!("unexpected input (nom {:?})", code)
+        }
+
+        impl<'a, T> NomResultExt<'a, T> for Result<(&'a str, T), $nom::Err<$nom::error::Error<&'a str>>> {
+            fn located_in(self, input: &'a str) -> Result<(&'a str, T), AocError> {
+                self.map_err(|err| match err {
+                    $nom::Err::Incomplete(_) => AocError::at(input, "", "incomplete input"),
+                    $nom::Err::Error(e) | $nom::Err::Failure(e) => AocError::at(input, e.input, nom_message(e.code)),
+                })
+            }
+        }
+
+        //
+        // Without the original input, the best we can do is show
+        // where the parse failed.
+        //
+        impl<'a> From<$nom::Err<$nom::error::Error<&'a str>>> for AocError {
+            fn from(err: $nom::Err<$nom::error::Error<&'a str>>) -> Self {
+                match err {
+                    $nom::Err::Incomplete(_) => AocError::new("incomplete input"),
+                    $nom::Err::Error(e) | $nom::Err::Failure(e) => {
+                        let context: String = e.input.chars().take(20).collect();
+                        AocError::new(format!("{} at {context:?}", nom_message(e.code)))
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "nom")]
+mod nom8_conversions {
+    use super::{AocError, NomResultExt};
+    nom_conversions!(nom);
+}
+
+#[cfg(feature = "nom7")]
+mod nom7_conversions {
+    use super::{AocError, NomResultExt};
+    nom_conversions!(nom7);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = "abc\ndef\nghi";
+        let err = AocError::at_offset(input, 5, "oops");
+        assert_eq!((err.line, err.column), (Some(2), Some(2)));
+        assert_eq!(err.to_string(), "line 2, column 2: oops");
+        let err = AocError::at(input, "ghi", "oops");
+        assert_eq!((err.line, err.column), (Some(3), Some(1)));
+        let err = AocError::at(input, "", "oops");
+        assert_eq!((err.line, err.column), (Some(3), Some(4)));
+        assert_eq!(AocError::at_cell((0, 4), "oops").to_string(), "line 1, column 5: oops");
+        assert_eq!(AocError::new("oops").on_line(7).to_string(), "line 7: oops");
+        assert_eq!(AocError::at_line(2, "oops").on_line(7).to_string(), "line 2: oops");
+    }
+
+    #[test]
+    fn test_conversions() {
+        let err: AocError = "12x".parse::<u32>().unwrap_err().into();
+        assert_eq!(err.to_string(), "invalid number: invalid digit found in string");
+        let err: AocError = "ab\nc\n".parse::<crate::Grid<u8>>().unwrap_err().into();
+        assert_eq!(err.to_string(), "line 2: expected 2 columns, found 1");
+        let err = "-".parse::<i32>().on_line(4).unwrap_err();
+        assert_eq!(err.to_string(), "line 4: invalid number: invalid digit found in string");
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn test_nom() {
+        use nom::{character::complete::{digit1, newline}, multi::separated_list1, IResult, Parser};
+        fn numbers(input: &str) -> IResult<&str, Vec<&str>> {
+            separated_list1(newline, digit1).parse(input)
+        }
+        let input = "12\n34\nx5";
+        let (rest, _) = numbers(input).located_in(input).unwrap();
+        assert_eq!(rest, "\nx5");
+        let err = (newline, digit1).parse(rest).located_in(input).map(|_| ()).unwrap_err();
+        assert_eq!((err.line, err.column), (Some(3), Some(1)));
+        let err: AocError = (newline, digit1).parse(rest).map(|_| ()).unwrap_err().into();
+        assert_eq!(err.message, "unexpected input (nom Digit) at \"x5\"");
+    }
+}
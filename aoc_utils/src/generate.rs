@@ -0,0 +1,198 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::grid::ORTHOGONAL;
+use crate::Grid;
+
+//
+// Random puzzle inputs.  Each day's generator produces input in the same
+// format as the real puzzle input, with the same properties the
+// solutions rely on (e.g. day 6's guard eventually leaves the map, and
+// day 17's program has an answer for part 2).  The same seed and size
+// always produce the same input.
+//
+// What "size" means varies by day; see `Generator::size`.
+//
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+pub struct Generator {
+    pub day: u32,
+    // What the size parameter controls, e.g. "side of the square grid"
+    pub size: &'static str,
+    // About the size of the real puzzle input
+    pub default_size: usize,
+    // Small enough to run every variant in a test
+    pub small_size: usize,
+    generate: fn(&mut StdRng, usize) -> String,
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (self.generate)(&mut rng, size)
+    }
+}
+
+macro_rules! generator {
+    ($day:literal, $module:ident, $size:literal, $default_size:literal, $small_size:literal) => {
+        Generator {
+            day: $day,
+            size: $size,
+            default_size: $default_size,
+            small_size: $small_size,
+            generate: $module::generate,
+        }
+    };
+}
+
+pub static GENERATORS: [Generator; 25] = [
+    generator!(1, day01, "lines", 1000, 50),
+    generator!(2, day02, "reports", 1000, 50),
+    generator!(3, day03, "bytes", 18000, 1000),
+    generator!(4, day04, "side of the square grid", 140, 20),
+    generator!(5, day05, "updates", 200, 20),
+    generator!(6, day06, "side of the square grid", 130, 20),
+    generator!(7, day07, "equations", 850, 30),
+    generator!(8, day08, "side of the square grid", 50, 20),
+    generator!(9, day09, "digits in the disk map", 19999, 201),
+    generator!(10, day10, "side of the square grid", 50, 20),
+    generator!(11, day11, "stones", 8, 4),
+    generator!(12, day12, "side of the square grid", 140, 20),
+    generator!(13, day13, "machines", 320, 20),
+    generator!(14, day14, "robots", 500, 50),
+    generator!(15, day15, "side of the square grid", 50, 10),
+    generator!(16, day16, "side of the square grid", 141, 21),
+    generator!(17, day17, "octal digits in register A", 16, 16),
+    generator!(18, day18, "largest coordinate (the solutions assume 70)", 70, 70),
+    generator!(19, day19, "designs", 400, 20),
+    generator!(20, day20, "side of the square grid", 141, 41),
+    generator!(21, day21, "codes", 5, 5),
+    generator!(22, day22, "buyers", 2000, 20),
+    generator!(23, day23, "computers", 520, 78),
+    generator!(24, day24, "bits in each operand", 45, 45),
+    generator!(25, day25, "locks and keys", 500, 20),
+];
+
+pub fn generator(day: u32) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<String> {
+    generator(day).map(|g| g.generate(seed, size))
+}
+
+//
+// Day 24's adder, like `generate(24, seed, bits)`, but with any number
+// of swapped pairs of gate outputs instead of the puzzle's 4.
+//
+pub fn adder(seed: u64, bits: usize, swapped_pairs: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    day24::adder(&mut rng, bits, swapped_pairs)
+}
+
+//
+// A perfect maze (exactly one path between any two open squares), made
+// by a randomized depth first search.  Open squares are at odd rows and
+// columns, plus the squares joining them; everything else is a wall.
+// The side should be odd.
+//
+fn maze(rng: &mut StdRng, side: usize) -> Grid<u8> {
+    let mut grid = Grid::new(side, side, b'#');
+    let mut stack = vec![(1, 1)];
+    grid[(1, 1)] = b'.';
+    while let Some(&(row, col)) = stack.last() {
+        let mut choices: Vec<(isize, isize)> = ORTHOGONAL.iter()
+            .copied()
+            .filter(|&(drow, dcol)| {
+                let next = grid.offset((row, col), (2 * drow, 2 * dcol));
+                next.is_some_and(|next| next.0 < side - 1 && next.1 < side - 1 && grid[next] == b'#')
+            })
+            .collect();
+        choices.shuffle(rng);
+        match choices.first() {
+            Some(&(drow, dcol)) => {
+                let between = grid.offset((row, col), (drow, dcol)).unwrap();
+                let next = grid.offset((row, col), (2 * drow, 2 * dcol)).unwrap();
+                grid[between] = b'.';
+                grid[next] = b'.';
+                stack.push(next);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_every_day() {
+        for (i, generator) in GENERATORS.iter().enumerate() {
+            assert_eq!(generator.day as usize, i + 1);
+            let input = generator.generate(1, generator.small_size);
+            assert!(input.ends_with('\n'), "day {}", generator.day);
+        }
+    }
+
+    #[test]
+    fn test_maze() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let grid = maze(&mut rng, 9);
+        let open: Vec<_> = grid.find_all(&b'.').collect();
+        // A perfect maze on 4x4 cells has 16 cells and 15 passages
+        assert_eq!(open.len(), 16 + 15);
+        assert!(open.iter().all(|&(row, col)| row % 2 == 1 || col % 2 == 1));
+    }
+
+    #[test]
+    fn test_adder() {
+        // Without swaps, every zNN but the final carry comes from an XOR
+        let input = adder(1, 8, 0);
+        let z_gates: Vec<&str> = input.lines().filter(|line| line.contains("-> z")).collect();
+        assert_eq!(z_gates.len(), 9);
+        assert_eq!(z_gates.iter().filter(|line| line.contains(" XOR ")).count(), 8);
+        // No more swaps than bits that can take one, and no more bits
+        // than there are wire names for
+        assert_eq!(adder(1, 8, 100).lines().count(), adder(1, 8, 0).lines().count());
+        assert!(generate(24, 1, 100_000).unwrap().contains("\nx3885: "));
+    }
+
+    #[test]
+    fn test_seeded() {
+        for generator in &GENERATORS {
+            let first = generator.generate(7, generator.small_size);
+            assert_eq!(first, generator.generate(7, generator.small_size), "day {}", generator.day);
+            assert_ne!(first, generator.generate(8, generator.small_size), "day {}", generator.day);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// Two columns of five digit location IDs.  Some IDs in the right column
+// are copied from the left so that part 2 has something to count.
+//
+pub fn generate(rng: &mut StdRng, lines: usize) -> String {
+    let left: Vec<u32> = (0..lines).map(|_| rng.random_range(10000..100000)).collect();
+    let mut output = String::new();
+    for &id in &left {
+        let right = if rng.random_bool(0.3) {
+            left[rng.random_range(0..lines)]
+        } else {
+            rng.random_range(10000..100000)
+        };
+        output.push_str(&format!("{id}   {right}\n"));
+    }
+    output
+}
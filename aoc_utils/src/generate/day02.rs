@@ -0,0 +1,28 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// Reports of 5 to 8 levels.  Most start out safe (steadily increasing
+// or decreasing by 1 to 3), then some get one or two levels disturbed
+// so that they're unsafe, or only safe with the Problem Dampener.
+//
+pub fn generate(rng: &mut StdRng, reports: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..reports {
+        let len = rng.random_range(5..=8);
+        let sign = if rng.random_bool(0.5) { 1 } else { -1 };
+        let mut levels = vec![rng.random_range(25..75)];
+        for _ in 1..len {
+            let last = levels[levels.len() - 1];
+            levels.push(last + sign * rng.random_range(1..=3));
+        }
+        for _ in 0..rng.random_range(0..=2) {
+            let i = rng.random_range(0..len);
+            levels[i] = (levels[i] + rng.random_range(-5..=5)).max(1);
+        }
+        let line: Vec<String> = levels.iter().map(i32::to_string).collect();
+        output.push_str(&line.join(" "));
+        output.push('\n');
+    }
+    output
+}
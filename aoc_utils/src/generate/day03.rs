@@ -0,0 +1,38 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+const JUNK: &[u8] = b"!@#$%^&*()[]{}<>,;:'?/+-_= \nabcdefghijklmnopqrstuvwxyz0123456789";
+
+//
+// Corrupted memory: random junk, with valid instructions sprinkled in,
+// and plenty of near misses that must not be mistaken for them.  The
+// result is about `bytes` long, in lines of about 3000 bytes.
+//
+pub fn generate(rng: &mut StdRng, bytes: usize) -> String {
+    let mut output = String::with_capacity(bytes + 32);
+    let mut line_length = 0;
+    while output.len() < bytes {
+        let piece = match rng.random_range(0..20) {
+            0..=3 => format!("mul({},{})", rng.random_range(0..1000), rng.random_range(0..1000)),
+            4 => "do()".to_string(),
+            5 => "don't()".to_string(),
+            6 => ["mul(", "mul[", "mul (", "do(", "don't", "mul(4*", "mul(1234,5)", "mul(6,7", "mul(8,9]", "why()", "undo()"]
+                .choose(rng).unwrap().to_string(),
+            _ => {
+                let len = rng.random_range(1..12);
+                (0..len).map(|_| *JUNK.choose(rng).unwrap() as char).filter(|&ch| ch != '\n').collect()
+            }
+        };
+        line_length += piece.len();
+        output.push_str(&piece);
+        if line_length > 3000 {
+            output.push('\n');
+            line_length = 0;
+        }
+    }
+    if !output.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
@@ -0,0 +1,48 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::grid::ALL_DIRECTIONS;
+
+//
+// A square grid of the letters X, M, A and S.  Random letters alone
+// rarely spell anything, so some XMAS words and X-MAS crosses are
+// planted as well.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let mut grid: Vec<Vec<u8>> = (0..side)
+        .map(|_| (0..side).map(|_| *b"XMAS".choose(rng).unwrap()).collect())
+        .collect();
+
+    let side = side as isize;
+    for _ in 0..side * side / 20 {
+        let row = rng.random_range(0..side as i64) as isize;
+        let col = rng.random_range(0..side as i64) as isize;
+        if rng.random_bool(0.5) {
+            let (drow, dcol) = *ALL_DIRECTIONS.choose(rng).unwrap();
+            let end = (row + 3 * drow, col + 3 * dcol);
+            if (0..side).contains(&end.0) && (0..side).contains(&end.1) {
+                for (i, &letter) in b"XMAS".iter().enumerate() {
+                    let i = i as isize;
+                    grid[(row + i * drow) as usize][(col + i * dcol) as usize] = letter;
+                }
+            }
+        } else if (1..side - 1).contains(&row) && (1..side - 1).contains(&col) {
+            let (row, col) = (row as usize, col as usize);
+            grid[row][col] = b'A';
+            let (first, second) = if rng.random_bool(0.5) { (b'M', b'S') } else { (b'S', b'M') };
+            grid[row - 1][col - 1] = first;
+            grid[row + 1][col + 1] = second;
+            let (first, second) = if rng.random_bool(0.5) { (b'M', b'S') } else { (b'S', b'M') };
+            grid[row - 1][col + 1] = first;
+            grid[row + 1][col - 1] = second;
+        }
+    }
+
+    let mut output = String::with_capacity(grid.len() * (grid.len() + 1));
+    for row in grid {
+        output.push_str(std::str::from_utf8(&row).unwrap());
+        output.push('\n');
+    }
+    output
+}
@@ -0,0 +1,37 @@
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+
+//
+// 49 two digit page numbers in a random total order, with a rule for
+// every pair of them, followed by `updates` lists of an odd number of
+// distinct pages.  About half of the updates are already in order.
+//
+pub fn generate(rng: &mut StdRng, updates: usize) -> String {
+    let mut order: Vec<u32> = (10..100).collect();
+    order.shuffle(rng);
+    order.truncate(49);
+
+    let mut rules = Vec::new();
+    for (i, &before) in order.iter().enumerate() {
+        for &after in &order[i + 1..] {
+            rules.push(format!("{before}|{after}\n"));
+        }
+    }
+    rules.shuffle(rng);
+
+    let mut output = rules.concat();
+    output.push('\n');
+    for _ in 0..updates {
+        let len = 2 * rng.random_range(2..12) + 1;
+        let mut pages: Vec<u32> = order.choose_multiple(rng, len).copied().collect();
+        pages.shuffle(rng);
+        if rng.random_bool(0.5) {
+            pages.sort_by_key(|page| order.iter().position(|p| p == page));
+        }
+        let pages: Vec<String> = pages.iter().map(u32::to_string).collect();
+        output.push_str(&pages.join(","));
+        output.push('\n');
+    }
+    output
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::{Direction, Grid};
+
+// How many maps to try; the one with the longest walk wins
+const ATTEMPTS: usize = 50;
+
+//
+// A square map with about 5% obstacles, and the guard somewhere in the
+// middle.  The solutions need the guard to eventually walk off the map,
+// so only maps where the guard does are candidates.  On most random maps
+// the guard leaves after a few turns, while the real input has a long
+// walk, so keep the candidate whose walk covers the most squares.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let side = side.max(4);
+    let mut best: Option<(usize, Grid<u8>)> = None;
+    let mut attempts = 0;
+    while attempts < ATTEMPTS || best.is_none() {
+        attempts += 1;
+        let mut grid = Grid::new(side, side, b'.');
+        for pos in grid.positions().collect::<Vec<_>>() {
+            if rng.random_bool(0.05) {
+                grid[pos] = b'#';
+            }
+        }
+        let guard = (rng.random_range(side / 4..=side * 3 / 4), rng.random_range(side / 4..=side * 3 / 4));
+        grid[guard] = b'^';
+        if let Some(visited) = leaves_map(&grid, guard) {
+            if best.as_ref().is_none_or(|(most, _)| visited > *most) {
+                best = Some((visited, grid));
+            }
+        }
+    }
+    best.unwrap().1.to_string()
+}
+
+//
+// How many squares the guard visits before leaving, or None if the guard
+// is stuck in a loop.
+//
+fn leaves_map(grid: &Grid<u8>, mut pos: (usize, usize)) -> Option<usize> {
+    let mut facing = Direction::North;
+    let mut seen = HashSet::new();
+    while seen.insert((pos, facing)) {
+        match grid.offset(pos, facing.delta()) {
+            None => return Some(seen.iter().map(|&(pos, _)| pos).collect::<HashSet<_>>().len()),
+            Some(ahead) if grid[ahead] == b'#' => facing = facing.turn_right(),
+            Some(ahead) => pos = ahead,
+        }
+    }
+    None
+}
@@ -0,0 +1,48 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// Equations of 2 to 12 operands of up to three digits.  About half of
+// the test values come from actually combining the operands with +, *
+// and concatenation; the rest are random, and probably impossible.
+//
+// Part 1 doesn't check for overflow as it tries every combination of
+// operators, so the operands are chosen to keep every combination well
+// within a u64.
+//
+pub fn generate(rng: &mut StdRng, equations: usize) -> String {
+    let mut output = String::new();
+    let mut count = 0;
+    while count < equations {
+        let len = rng.random_range(2..=12);
+        let operands: Vec<u64> = (0..len)
+            .map(|_| match rng.random_range(0..3) {
+                0 => rng.random_range(1..10),
+                1 => rng.random_range(1..100),
+                _ => rng.random_range(1..1000),
+            })
+            .collect();
+        let bound = operands.iter().try_fold(1u64, |bound, &n| bound.checked_mul(n + 1));
+        if bound.is_none_or(|bound| bound > 100_000_000_000_000) {
+            continue;
+        }
+
+        let target = if rng.random_bool(0.5) {
+            operands[1..].iter().try_fold(operands[0], |acc, &n| match rng.random_range(0..3) {
+                0 => acc.checked_add(n),
+                1 => acc.checked_mul(n),
+                _ => acc.checked_mul(10u64.pow(n.ilog10() + 1))?.checked_add(n),
+            })
+        } else {
+            Some(rng.random_range(1..bound.unwrap()))
+        };
+        let Some(target) = target.filter(|&target| target < 1_000_000_000_000_000) else {
+            continue;
+        };
+
+        let operands: Vec<String> = operands.iter().map(u64::to_string).collect();
+        output.push_str(&format!("{target}: {}\n", operands.join(" ")));
+        count += 1;
+    }
+    output
+}
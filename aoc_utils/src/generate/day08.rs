@@ -0,0 +1,24 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::Grid;
+
+const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+//
+// A square map with a few antennas on each of several frequencies.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let mut grid = Grid::new(side, side, b'.');
+    let frequencies = (side / 2).clamp(1, FREQUENCIES.len());
+    for &frequency in FREQUENCIES.choose_multiple(rng, frequencies) {
+        for _ in 0..rng.random_range(2..=4) {
+            let pos = (rng.random_range(0..side), rng.random_range(0..side));
+            if grid[pos] == b'.' {
+                grid[pos] = frequency;
+            }
+        }
+    }
+    grid.to_string()
+}
@@ -0,0 +1,23 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// A disk map: alternating file and free space lengths, starting and
+// ending with a file.  Files are 1 to 9 blocks; free space may be empty.
+// File IDs have to fit in a u16 (with one value left over), which
+// limits the number of digits.
+//
+pub fn generate(rng: &mut StdRng, digits: usize) -> String {
+    let digits = digits.clamp(1, 2 * (u16::MAX as usize - 1) - 1);
+    let mut output: String = (0..digits)
+        .map(|i| {
+            let min = if i % 2 == 0 { 1 } else { 0 };
+            char::from_digit(rng.random_range(min..=9), 10).unwrap()
+        })
+        .collect();
+    if digits.is_multiple_of(2) {
+        output.pop();
+    }
+    output.push('\n');
+    output
+}
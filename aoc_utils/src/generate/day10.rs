@@ -0,0 +1,24 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::Grid;
+
+//
+// A square topographic map.  Random heights would hardly ever make a
+// trail, so the map is a range of cone-shaped peaks (each step away
+// from a peak is one lower), with some noise.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let peaks: Vec<(usize, usize)> = (0..(side * side / 40).max(1))
+        .map(|_| (rng.random_range(0..side), rng.random_range(0..side)))
+        .collect();
+    let mut grid = Grid::new(side, side, 0u8);
+    for pos in grid.positions().collect::<Vec<_>>() {
+        let height = peaks.iter()
+            .map(|&(row, col)| 9usize.saturating_sub(row.abs_diff(pos.0) + col.abs_diff(pos.1)))
+            .max()
+            .unwrap();
+        grid[pos] = if rng.random_bool(0.1) { rng.random_range(0..=9) } else { height as u8 };
+    }
+    grid.map(|&height| b'0' + height).to_string()
+}
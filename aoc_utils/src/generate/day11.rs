@@ -0,0 +1,15 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// A line of stones with numbers of up to seven digits.
+//
+pub fn generate(rng: &mut StdRng, stones: usize) -> String {
+    let stones: Vec<String> = (0..stones.max(1))
+        .map(|_| {
+            let digits = rng.random_range(1..=7);
+            rng.random_range(0..10u64.pow(digits)).to_string()
+        })
+        .collect();
+    format!("{}\n", stones.join(" "))
+}
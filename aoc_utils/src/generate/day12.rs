@@ -0,0 +1,25 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::Grid;
+
+//
+// A square map of garden plots.  Start with random plants, then
+// repeatedly let plots take on a neighbor's plant, so that regions
+// of all sorts of sizes and shapes form.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let mut grid = Grid::new(side, side, b'A');
+    for pos in grid.positions().collect::<Vec<_>>() {
+        grid[pos] = rng.random_range(b'A'..=b'Z');
+    }
+    for _ in 0..4 * side * side {
+        let pos = (rng.random_range(0..side), rng.random_range(0..side));
+        let neighbors: Vec<_> = grid.neighbors4(pos).collect();
+        if let Some(&neighbor) = neighbors.choose(rng) {
+            grid[pos] = grid[neighbor];
+        }
+    }
+    grid.to_string()
+}
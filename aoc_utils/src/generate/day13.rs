@@ -0,0 +1,31 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// Claw machines with buttons that move 10 to 99 in each direction.
+// About half of the prizes can be won; the rest are random.  The
+// buttons never move in the same direction, which the solutions
+// can't handle.
+//
+pub fn generate(rng: &mut StdRng, machines: usize) -> String {
+    let mut output = Vec::new();
+    while output.len() < machines {
+        let (ax, ay) = (rng.random_range(10..100i64), rng.random_range(10..100i64));
+        let (bx, by) = (rng.random_range(10..100i64), rng.random_range(10..100i64));
+        if ax * by == ay * bx {
+            continue;
+        }
+        let (px, py) = if rng.random_bool(0.5) {
+            let (a, b) = (rng.random_range(0..=100), rng.random_range(0..=100));
+            (a * ax + b * bx, a * ay + b * by)
+        } else {
+            (rng.random_range(1000..20000), rng.random_range(1000..20000))
+        };
+        output.push(format!("\
+Button A: X+{ax}, Y+{ay}
+Button B: X+{bx}, Y+{by}
+Prize: X={px}, Y={py}
+"));
+    }
+    output.join("\n")
+}
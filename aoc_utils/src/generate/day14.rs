@@ -0,0 +1,16 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// Robots somewhere in the 101 by 103 room, with velocities of up to
+// 99 tiles per second in each direction.
+//
+pub fn generate(rng: &mut StdRng, robots: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..robots {
+        let (px, py) = (rng.random_range(0..101), rng.random_range(0..103));
+        let (vx, vy) = (rng.random_range(-99..100), rng.random_range(-99..100));
+        output.push_str(&format!("p={px},{py} v={vx},{vy}\n"));
+    }
+    output
+}
@@ -0,0 +1,34 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use crate::Grid;
+
+//
+// A square warehouse surrounded by walls, with some more walls and
+// lots of boxes inside, followed by 8 moves per square of the
+// warehouse, in lines of 1000.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let side = side.max(4);
+    let mut grid = Grid::new(side, side, b'.');
+    for pos in grid.positions().collect::<Vec<_>>() {
+        let edge = pos.0 == 0 || pos.1 == 0 || pos.0 == side - 1 || pos.1 == side - 1;
+        grid[pos] = match rng.random_range(0..100) {
+            _ if edge => b'#',
+            0..10 => b'#',
+            10..45 => b'O',
+            _ => b'.',
+        };
+    }
+    grid[(side / 2, side / 2)] = b'@';
+
+    let mut output = grid.to_string();
+    output.push('\n');
+    let moves: String = (0..8 * side * side).map(|_| *b"<>^v".choose(rng).unwrap() as char).collect();
+    for line in moves.as_bytes().chunks(1000) {
+        output.push_str(std::str::from_utf8(line).unwrap());
+        output.push('\n');
+    }
+    output
+}
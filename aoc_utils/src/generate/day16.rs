@@ -0,0 +1,21 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// A maze with the start in the bottom left corner and the end in the
+// top right, like the real puzzle.  Some extra walls are knocked down
+// so there are many paths, some of them equally good.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let side = (side.max(5) - 1) / 2 * 2 + 1;
+    let mut grid = super::maze(rng, side);
+    for _ in 0..side * side / 40 {
+        let pos = (rng.random_range(1..side - 1), rng.random_range(1..side - 1));
+        if (pos.0 + pos.1) % 2 == 1 {
+            grid[pos] = b'.';
+        }
+    }
+    grid[(side - 2, 1)] = b'S';
+    grid[(1, side - 2)] = b'E';
+    grid.to_string()
+}
@@ -0,0 +1,83 @@
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+//
+// The real programs all have the same shape:
+//
+//      B = A % 8
+//      B ^= <first>
+//      C = A >> B
+//      B ^= <second>
+//      B ^= C
+//      output B % 8
+//      A >>= 3
+//      loop while A != 0
+//
+// with the instructions in the middle in slightly different orders.
+// Not every pair of constants has a register A that makes the program
+// print itself, and part 2's digit-by-digit search can get stuck even
+// when one does.  So run that search here (with a limit), and try
+// other constants if it doesn't finish.
+//
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.clamp(1, 21);
+    loop {
+        let first = rng.random_range(1..8);
+        let second = rng.random_range(1..8);
+        let ignored = rng.random_range(0..8);
+        let layouts = [
+            [2, 4, 1, first, 7, 5, 1, second, 4, ignored, 5, 5, 0, 3, 3, 0],
+            [2, 4, 1, first, 7, 5, 4, ignored, 1, second, 5, 5, 0, 3, 3, 0],
+            [2, 4, 1, first, 7, 5, 0, 3, 4, ignored, 1, second, 5, 5, 3, 0],
+        ];
+        let program = layouts.choose(rng).unwrap();
+        if quine(program, first, second).is_none() {
+            continue;
+        }
+
+        let a = rng.random_range(8u64.pow(size as u32 - 1)..8u64.pow(size as u32));
+        let program = program.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",");
+        return format!("Register A: {a}\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n");
+    }
+}
+
+fn run(mut a: u64, first: u64, second: u64) -> Vec<u64> {
+    let mut output = Vec::new();
+    loop {
+        let b = (a % 8) ^ first;
+        output.push((b ^ second ^ (a >> b)) % 8);
+        a >>= 3;
+        if a == 0 {
+            return output;
+        }
+    }
+}
+
+//
+// The same search as day 17's part 2: find the octal digits of A from
+// the most significant down, then count up until the output matches.
+//
+fn quine(program: &[u64], first: u64, second: u64) -> Option<u64> {
+    let mut steps = 0;
+    let mut place_value = 8u64.pow(program.len() as u32 - 1);
+    let mut a = place_value;
+    for digit in (0..program.len()).rev() {
+        while run(a, first, second).get(digit) != Some(&program[digit]) {
+            a += place_value;
+            steps += 1;
+            if steps > 1000 || a >= 8 * 8u64.pow(program.len() as u32 - 1) {
+                return None;
+            }
+        }
+        place_value /= 8;
+    }
+    while run(a, first, second) != program {
+        a += 1;
+        steps += 1;
+        if steps > 10_000 {
+            return None;
+        }
+    }
+    Some(a)
+}
@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::Grid;
+
+// The solutions assume the first 1024 bytes leave a path open
+const FIRST_BYTES: usize = 1024;
+
+//
+// Bytes fall on distinct squares in a random order.  Keep going a while
+// after the byte that first cuts off the exit, like the real input.
+//
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let side = size.max(2) + 1;
+    let mut squares: Vec<(usize, usize)> = (0..side)
+        .flat_map(|x| (0..side).map(move |y| (x, y)))
+        .filter(|&square| square != (0, 0) && square != (side - 1, side - 1))
+        .collect();
+    loop {
+        squares.shuffle(rng);
+        let blocking = first_blocking(&squares, side);
+        if blocking >= FIRST_BYTES.min(squares.len() / 4) {
+            let count = rng.random_range(blocking + 1..=squares.len());
+            return squares[..count].iter().map(|(x, y)| format!("{x},{y}\n")).collect();
+        }
+    }
+}
+
+fn has_path(bytes: &[(usize, usize)], side: usize) -> bool {
+    let mut blocked = Grid::new(side, side, false);
+    for &(x, y) in bytes {
+        blocked[(y, x)] = true;
+    }
+    let mut queue = VecDeque::from([(0, 0)]);
+    blocked[(0, 0)] = true;
+    while let Some(pos) = queue.pop_front() {
+        if pos == (side - 1, side - 1) {
+            return true;
+        }
+        for next in blocked.neighbors4(pos).collect::<Vec<_>>() {
+            if !blocked[next] {
+                blocked[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    false
+}
+
+//
+// The index of the first byte that leaves no path to the exit.
+//
+fn first_blocking(bytes: &[(usize, usize)], side: usize) -> usize {
+    let (mut open, mut closed) = (0, bytes.len());
+    while closed - open > 1 {
+        let middle = (open + closed) / 2;
+        if has_path(&bytes[..middle], side) {
+            open = middle;
+        } else {
+            closed = middle;
+        }
+    }
+    closed - 1
+}
@@ -0,0 +1,75 @@
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+
+const COLORS: &[u8] = b"wubrg";
+
+// How much work part 1's (unmemoized) search may waste on dead ends
+const MAX_DEAD_ENDS: usize = 10_000;
+
+//
+// Towels of up to 8 stripes, with every color but one available as a
+// single stripe.  That color can only be matched as part of a longer
+// towel, which is what makes some of the designs impossible.
+//
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let missing = *COLORS.choose(rng).unwrap();
+    let mut towels = BTreeSet::new();
+    for &color in COLORS {
+        if color != missing {
+            towels.insert(vec![color]);
+        }
+    }
+    let count = (size + 50).min(450);
+    while towels.len() < count {
+        let len = rng.random_range(2..=8);
+        towels.insert((0..len).map(|_| *COLORS.choose(rng).unwrap()).collect::<Vec<u8>>());
+    }
+    let towels: Vec<Vec<u8>> = towels.into_iter().collect();
+
+    let mut designs = Vec::new();
+    while designs.len() < size {
+        let len = rng.random_range(20..=60);
+        let mut design = Vec::new();
+        while design.len() < len {
+            design.extend(towels.choose(rng).unwrap());
+        }
+        if rng.random_bool(0.5) {
+            let pos = rng.random_range(0..design.len());
+            design.insert(pos, missing);
+        }
+        if dead_ends(&design, &towels) <= MAX_DEAD_ENDS {
+            designs.push(String::from_utf8(design).unwrap());
+        }
+    }
+
+    let mut towels: Vec<String> = towels.into_iter().map(|towel| String::from_utf8(towel).unwrap()).collect();
+    towels.shuffle(rng);
+    format!("{}\n\n{}\n", towels.join(", "), designs.join("\n"))
+}
+
+//
+// A search that doesn't remember its results tries every way of making
+// each prefix of the design, and fails for every one of them if the rest
+// of the design can't be made.  Count those failures.
+//
+fn dead_ends(design: &[u8], towels: &[Vec<u8>]) -> usize {
+    let len = design.len();
+    let mut can_finish = vec![false; len + 1];
+    can_finish[len] = true;
+    for i in (0..len).rev() {
+        can_finish[i] = towels.iter().any(|t| design[i..].starts_with(t) && can_finish[i + t.len()]);
+    }
+    let mut ways = vec![0usize; len + 1];
+    ways[0] = 1;
+    for i in 0..len {
+        for t in towels {
+            if design[i..].starts_with(t) {
+                ways[i + t.len()] = ways[i + t.len()].saturating_add(ways[i]);
+            }
+        }
+    }
+    (0..=len).filter(|&i| !can_finish[i]).fold(0, |total: usize, i| total.saturating_add(ways[i]))
+}
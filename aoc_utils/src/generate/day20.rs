@@ -0,0 +1,41 @@
+use rand::rngs::StdRng;
+
+use crate::Grid;
+
+//
+// A racetrack: a single winding path from S to E, with no branches.
+// It's the path between two corners of a maze.
+//
+pub fn generate(rng: &mut StdRng, side: usize) -> String {
+    let side = (side.max(5) - 1) / 2 * 2 + 1;
+    let maze = super::maze(rng, side);
+    let start = (side - 2, 1);
+    let end = (1, side - 2);
+
+    // Find the path from start to end with a depth first search.
+    let mut path = vec![start];
+    let mut seen = Grid::new(side, side, false);
+    seen[start] = true;
+    while let Some(&pos) = path.last() {
+        if pos == end {
+            break;
+        }
+        match maze.neighbors4(pos).find(|&next| maze[next] == b'.' && !seen[next]) {
+            Some(next) => {
+                seen[next] = true;
+                path.push(next);
+            }
+            None => {
+                path.pop();
+            }
+        }
+    }
+
+    let mut grid = Grid::new(side, side, b'#');
+    for pos in path {
+        grid[pos] = b'.';
+    }
+    grid[start] = b'S';
+    grid[end] = b'E';
+    grid.to_string()
+}
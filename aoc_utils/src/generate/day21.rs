@@ -0,0 +1,9 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// Three digits and an A, like "029A".
+//
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size).map(|_| format!("{:03}A\n", rng.random_range(1..1000))).collect()
+}
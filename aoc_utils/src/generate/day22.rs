@@ -0,0 +1,10 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// One initial secret number per buyer.  Secrets are pruned to 24 bits,
+// so that's the range of the initial ones too.
+//
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size).map(|_| format!("{}\n", rng.random_range(1..1 << 24))).collect()
+}
@@ -0,0 +1,47 @@
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+// The computers form groups of this size
+const GROUP: usize = 13;
+
+//
+// Computers in groups of 13.  One group is fully connected (the LAN
+// party); every other group is missing one connection, so its largest
+// sets of connected computers only have 12.  Each computer also has a
+// connection or so outside its group, which are too sparse to form
+// anything bigger than a triangle with them.
+//
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut names: Vec<String> = (b'a'..=b'z')
+        .flat_map(|first| (b'a'..=b'z').map(move |second| String::from_utf8(vec![first, second]).unwrap()))
+        .collect();
+    names.shuffle(rng);
+    let groups = (size / GROUP).clamp(2, names.len() / GROUP);
+    names.truncate(groups * GROUP);
+
+    let mut connections = Vec::new();
+    for (i, group) in names.chunks(GROUP).enumerate() {
+        for a in 0..GROUP {
+            for b in a + 1..GROUP {
+                // All but the first group lose their first connection
+                if i == 0 || (a, b) != (0, 1) {
+                    connections.push((&group[a], &group[b]));
+                }
+            }
+        }
+    }
+    let mut outside = BTreeSet::new();
+    for i in 0..names.len() {
+        let j = rng.random_range(0..names.len());
+        if i / GROUP != j / GROUP {
+            outside.insert((i.min(j), i.max(j)));
+        }
+    }
+    connections.extend(outside.into_iter().map(|(i, j)| (&names[i], &names[j])));
+
+    connections.shuffle(rng);
+    connections.iter().map(|(a, b)| format!("{a}-{b}\n")).collect()
+}
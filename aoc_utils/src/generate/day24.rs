@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::Rng;
+
+// The puzzle swaps 4 pairs of gate outputs
+const SWAPPED_PAIRS: usize = 4;
+
+// Each bit after the first needs 4 new wire names, and there are only
+// 23 * 26 * 26 names that don't clash with the x, y and z registers
+const MAX_BITS: usize = (23 * 26 * 26 - 1) / 4;
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    adder(rng, size, SWAPPED_PAIRS)
+}
+
+//
+// A ripple-carry adder for two numbers of `bits` bits (at least 6, and
+// at most MAX_BITS), with `swapped_pairs` pairs of gate outputs swapped
+// (at most one per bit, other than the first and last).  Bit 0 is a
+// half adder; every other bit is a
+// full adder built from 5 gates:
+//
+//      xNN XOR yNN -> sum
+//      xNN AND yNN -> both
+//      carry XOR sum -> zNN
+//      carry AND sum -> either
+//      both OR either -> carry out
+//
+// Each swap is in a different bit, and is one of the kinds in the real
+// puzzle: zNN with one of the bit's AND or OR outputs, or the two
+// outputs of the gates that read xNN and yNN.  (Day 24's part 2 only
+// handles 45 bit numbers.)
+//
+pub fn adder(rng: &mut StdRng, bits: usize, swapped_pairs: usize) -> String {
+    let bits = bits.clamp(6, MAX_BITS);
+    let mut used = BTreeSet::new();
+    let mut wire = || loop {
+        let name: String = [b"abcdefghijklmnopqrstuvw".choose(rng).unwrap()]
+            .into_iter()
+            .chain([(); 2].map(|_| b"abcdefghijklmnopqrstuvwxyz".choose(rng).unwrap()))
+            .map(|&b| b as char)
+            .collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    struct Gate {
+        inputs: [String; 2],
+        operation: &'static str,
+        output: String,
+    }
+    let gate = |a: &str, operation, b: &str, output: &str| Gate {
+        inputs: [a.to_string(), b.to_string()],
+        operation,
+        output: output.to_string(),
+    };
+
+    // The gate indices within each bit that may be swapped
+    let mut bits_with_gates = Vec::new();
+    let mut gates = vec![
+        gate("x00", "XOR", "y00", "z00"),
+    ];
+    let mut carry = wire();
+    gates.push(gate("x00", "AND", "y00", &carry));
+    for bit in 1..bits {
+        let (sum, both, either) = (wire(), wire(), wire());
+        let carry_out = if bit == bits - 1 { format!("z{bits:02}") } else { wire() };
+        let first = gates.len();
+        gates.push(gate(&format!("x{bit:02}"), "XOR", &format!("y{bit:02}"), &sum));
+        gates.push(gate(&format!("x{bit:02}"), "AND", &format!("y{bit:02}"), &both));
+        gates.push(gate(&carry, "XOR", &sum, &format!("z{bit:02}")));
+        gates.push(gate(&carry, "AND", &sum, &either));
+        gates.push(gate(&both, "OR", &either, &carry_out));
+        bits_with_gates.push(first);
+        carry = carry_out;
+    }
+
+    // Leave the last bit alone, so the final carry stays in place
+    bits_with_gates.pop();
+    for &first in bits_with_gates.choose_multiple(rng, swapped_pairs).collect::<Vec<_>>() {
+        let (a, b) = match rng.random_range(0..4) {
+            0 => (first, first + 1),        // sum <-> both
+            1 => (first + 2, first + 1),    // zNN <-> both
+            2 => (first + 2, first + 3),    // zNN <-> either
+            _ => (first + 2, first + 4),    // zNN <-> carry out
+        };
+        let output = std::mem::take(&mut gates[a].output);
+        gates[a].output = std::mem::replace(&mut gates[b].output, output);
+    }
+
+    let mut input = String::new();
+    for register in ["x", "y"] {
+        for bit in 0..bits {
+            input += &format!("{register}{bit:02}: {}\n", rng.random_range(0..2));
+        }
+    }
+    input += "\n";
+    gates.shuffle(rng);
+    for mut gate in gates {
+        gate.inputs.shuffle(rng);
+        input += &format!("{} {} {} -> {}\n", gate.inputs[0], gate.operation, gate.inputs[1], gate.output);
+    }
+    input
+}
@@ -0,0 +1,22 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+//
+// Locks have a solid top row, and keys a solid bottom row.  In between,
+// each of the 5 columns has a random height from 0 to 5.
+//
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let schematics: Vec<String> = (0..size).map(|_| {
+        let lock = rng.random_bool(0.5);
+        let heights: Vec<usize> = (0..5).map(|_| rng.random_range(0..=5)).collect();
+        (0..7).map(|row| {
+            let line: String = heights.iter().map(|&height| {
+                // Distance into the schematic from the solid row
+                let depth = if lock { row } else { 6 - row };
+                if depth <= height { '#' } else { '.' }
+            }).collect();
+            line + "\n"
+        }).collect()
+    }).collect();
+    schematics.join("\n")
+}
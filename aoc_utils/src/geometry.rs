@@ -0,0 +1,370 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+//
+// A point (or vector) in two dimensions.  Like the puzzle inputs, y
+// increases going down, so North is (0, -1).
+//
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    //
+    // The number of orthogonal steps between the two points.
+    //
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    //
+    // The number of steps between the two points when diagonal
+    // steps are allowed.
+    //
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Point { x: self.x + rhs.x, y: self.y + rhs.y }
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Point { x: self.x - rhs.x, y: self.y - rhs.y }
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Point { x: self.x * rhs, y: self.y * rhs }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point { x: -self.x, y: -self.y }
+    }
+}
+
+impl<T: Add<Output = T> + From<i8>> Add<Direction> for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self {
+        self + rhs.offset()
+    }
+}
+
+impl<T: AddAssign + From<i8>> AddAssign<Direction> for Point<T> {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self += rhs.offset();
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDirection(pub char);
+
+impl Display for InvalidDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a direction", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDirection {}
+
+//
+// The four cardinal directions.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    //
+    // A single step in this direction, as a vector.
+    //
+    pub fn offset<T: From<i8>>(self) -> Point<T> {
+        let (dx, dy) = match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        };
+        Point { x: T::from(dx), y: T::from(dy) }
+    }
+
+    //
+    // A single step in this direction, as (row, column) for use with `Grid`.
+    //
+    pub fn delta(self) -> (isize, isize) {
+        let Point { x, y } = self.offset::<isize>();
+        (y, x)
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+//
+// Accepts either arrows (`^>v<`) or compass letters (`NESW`).
+//
+impl TryFrom<char> for Direction {
+    type Error = InvalidDirection;
+
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            '^' | 'N' => Ok(Direction::North),
+            '>' | 'E' => Ok(Direction::East),
+            'v' | 'S' => Ok(Direction::South),
+            '<' | 'W' => Ok(Direction::West),
+            _ => Err(InvalidDirection(ch)),
+        }
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = InvalidDirection;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        Direction::try_from(byte as char)
+    }
+}
+
+//
+// The four cardinal and four diagonal directions, clockwise from North.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North, Direction8::NorthEast, Direction8::East, Direction8::SouthEast,
+        Direction8::South, Direction8::SouthWest, Direction8::West, Direction8::NorthWest,
+    ];
+    pub const ORTHOGONAL: [Direction8; 4] =
+        [Direction8::North, Direction8::East, Direction8::South, Direction8::West];
+    pub const DIAGONAL: [Direction8; 4] =
+        [Direction8::NorthEast, Direction8::SouthEast, Direction8::SouthWest, Direction8::NorthWest];
+
+    fn rotate(self, eighths: usize) -> Self {
+        Direction8::ALL[(self as usize + eighths) % 8]
+    }
+
+    //
+    // Turns 45 degrees.
+    //
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub fn offset<T: From<i8>>(self) -> Point<T> {
+        let (dx, dy) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+        Point { x: T::from(dx), y: T::from(dy) }
+    }
+
+    pub fn delta(self) -> (isize, isize) {
+        let Point { x, y } = self.offset::<isize>();
+        (y, x)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => Direction8::North,
+            Direction::East => Direction8::East,
+            Direction::South => Direction8::South,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 5);
+        assert_eq!(a + b, Point::new(4, 3));
+        assert_eq!(a - b, Point::new(2, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(3, 2));
+        assert_eq!(c.to_string(), "3,2");
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1i32, 1);
+        let b = Point::new(4, -3);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        let c = Point::new(2usize, 9);
+        let d = Point::new(5usize, 1);
+        assert_eq!(c.manhattan_distance(&d), 11);
+        assert_eq!(d.chebyshev_distance(&c), 8);
+    }
+
+    #[test]
+    fn test_direction_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.offset::<i32>() + direction.reverse().offset(), Point::new(0, 0));
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.delta(), (-1, 0));
+        assert_eq!(Direction::West.delta(), (0, -1));
+    }
+
+    #[test]
+    fn test_direction_parse() {
+        let arrows: Vec<Direction> = "^>v<".chars().map(|ch| ch.try_into().unwrap()).collect();
+        let letters: Vec<Direction> = "NESW".chars().map(|ch| ch.try_into().unwrap()).collect();
+        assert_eq!(arrows, Direction::ALL);
+        assert_eq!(letters, Direction::ALL);
+        assert_eq!(Direction::try_from(b'v'), Ok(Direction::South));
+        assert_eq!(Direction::try_from('x'), Err(InvalidDirection('x')));
+        let redrawn: String = arrows.iter().map(|d| d.arrow()).collect();
+        assert_eq!(redrawn, "^>v<");
+    }
+
+    #[test]
+    fn test_point_plus_direction() {
+        let mut p = Point::new(5i64, 5);
+        p += Direction::North;
+        assert_eq!(p, Point::new(5, 4));
+        assert_eq!(p + Direction::East + Direction::East, Point::new(7, 4));
+    }
+
+    #[test]
+    fn test_direction8() {
+        for direction in Direction8::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            let Point { x, y } = direction.offset::<i32>();
+            assert_eq!(direction.is_diagonal(), x != 0 && y != 0);
+        }
+        assert_eq!(Direction8::North.turn_right(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.delta(), (1, -1));
+        assert_eq!(Direction8::from(Direction::West), Direction8::West);
+    }
+}
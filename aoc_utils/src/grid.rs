@@ -0,0 +1,428 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::AocError;
+
+//
+// A position in a grid, as (row, column).  Row 0 is at the top.
+//
+pub type Pos = (usize, usize);
+
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const DIAGONAL: [(isize, isize); 4] = [(-1, 1), (1, 1), (1, -1), (-1, -1)];
+pub const ALL_DIRECTIONS: [(isize, isize); 8] =
+    [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+//
+// A dense, rectangular grid of cells, stored row by row.
+//
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedGridError {
+    pub line: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for RaggedGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: expected {} columns, found {}", self.line, self.expected, self.found)
+    }
+}
+
+impl std::error::Error for RaggedGridError {}
+
+//
+// Why a string isn't a grid of bytes: the lines are different lengths,
+// or there's a character that doesn't fit in a byte.  `line` and `col`
+// start at 1.
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    Ragged(RaggedGridError),
+    NotAscii { line: usize, col: usize, ch: char },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Ragged(err) => err.fmt(f),
+            ParseGridError::NotAscii { line, col, ch } => write!(f, "line {line}, column {col}: {ch:?} is not ASCII"),
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl From<RaggedGridError> for ParseGridError {
+    fn from(err: RaggedGridError) -> Self {
+        ParseGridError::Ragged(err)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { rows, cols, cells: vec![fill; rows * cols] }
+    }
+
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), rows * cols, "wrong number of cells");
+        Grid { rows, cols, cells }
+    }
+
+    //
+    // Parse one row per line, converting each character with `f`.
+    // All lines must be the same length.
+    //
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, RaggedGridError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut rows = 0;
+        let mut cols = 0;
+        for (i, line) in input.lines().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let found = cells.len() - before;
+            if i == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(RaggedGridError { line: i + 1, expected: cols, found });
+            }
+            rows += 1;
+        }
+        Ok(Grid { rows, cols, cells })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        if row < self.rows && col < self.cols {
+            Some(&self.cells[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        if row < self.rows && col < self.cols {
+            Some(&mut self.cells[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    //
+    // Access with coordinates that may be negative or past the far edge,
+    // in which case they wrap around to the other side.  There's nothing
+    // to wrap around to in an empty grid, so that panics.
+    //
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        assert!(!self.cells.is_empty(), "get_wrapping on an empty grid");
+        let row = row.rem_euclid(self.rows as isize) as usize;
+        let col = col.rem_euclid(self.cols as isize) as usize;
+        &self.cells[row * self.cols + col]
+    }
+
+    //
+    // The position `delta` away from `pos`, if it is within the grid.
+    //
+    pub fn offset(&self, (row, col): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let row = row.checked_add_signed(dr)?;
+        let col = col.checked_add_signed(dc)?;
+        if row < self.rows && col < self.cols {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.into_iter().filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_DIRECTIONS.into_iter().filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    //
+    // The positions starting at `start` and repeatedly stepping by `delta`,
+    // until leaving the grid.
+    //
+    pub fn line(&self, start: Pos, delta: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        let first = self.get(start).map(|_| start);
+        std::iter::successors(first, move |&pos| self.offset(pos, delta))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.cols, "column {col} is outside the grid");
+        self.cells[col..].iter().step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    //
+    // The diagonals running down and to the right, starting with the one
+    // in the bottom left corner.  Each diagonal is a list of positions.
+    //
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = Pos> + '_> + '_ {
+        // A grid with no columns has no diagonals, however many rows it has
+        let rows = if self.cols == 0 { 0 } else { self.rows };
+        let left = (0..rows).rev().map(|row| (row, 0));
+        let top = (1..self.cols).map(|col| (0, col));
+        left.chain(top).map(|start| self.line(start, (1, 1)))
+    }
+
+    //
+    // The diagonals running down and to the left, starting with the one
+    // in the top left corner.
+    //
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = Pos> + '_> + '_ {
+        let rows = if self.cols == 0 { 0 } else { self.rows };
+        let top = (0..self.cols).map(|col| (0, col));
+        let right = (1..rows).map(|row| (row, self.cols - 1));
+        top.chain(right).map(|start| self.line(start, (1, -1)))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter().filter(move |(_, v)| *v == value).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { rows: self.rows, cols: self.cols, cells: self.cells.iter().map(f).collect() }
+    }
+
+    //
+    // A copy of the grid surrounded by a one cell border of `sentinel`.
+    // A position (row, col) in the original is (row+1, col+1) in the copy,
+    // so that every original position has neighbors in all directions.
+    //
+    pub fn with_border(&self, sentinel: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut result = Grid::new(self.rows + 2, self.cols + 2, sentinel);
+        for (row, values) in self.iter_rows().enumerate() {
+            let start = (row + 1) * result.cols + 1;
+            result.cells[start..start + self.cols].clone_from_slice(values);
+        }
+        result
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        assert!(row < self.rows && col < self.cols, "({row}, {col}) is outside the grid");
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        assert!(row < self.rows && col < self.cols, "({row}, {col}) is outside the grid");
+        &mut self.cells[row * self.cols + col]
+    }
+}
+
+impl Grid<u8> {
+    //
+    // Parse a non-empty grid of characters, all of which must satisfy
+    // `valid`.  Puzzle maps are small enough that the characters are
+    // always ASCII, so each one is stored as a byte.
+    //
+    pub fn parse_checked(input: &str, valid: impl Fn(char) -> bool) -> Result<Self, AocError> {
+        for (row, line) in input.lines().enumerate() {
+            if let Some((col, ch)) = line.chars().enumerate().find(|&(_, ch)| !ch.is_ascii() || !valid(ch)) {
+                return Err(AocError::at_cell((row, col), format!("unexpected {ch:?}")));
+            }
+        }
+        let grid: Grid<u8> = input.parse()?;
+        if grid.rows == 0 || grid.cols == 0 {
+            return Err(AocError::new("empty grid"));
+        }
+        Ok(grid)
+    }
+}
+
+impl std::str::FromStr for Grid<u8> {
+    type Err = ParseGridError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        for (row, line) in input.lines().enumerate() {
+            if let Some((col, ch)) = line.chars().enumerate().find(|(_, ch)| !ch.is_ascii()) {
+                return Err(ParseGridError::NotAscii { line: row + 1, col: col + 1, ch });
+            }
+        }
+        Ok(Grid::parse(input, |ch| ch as u8)?)
+    }
+}
+
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.iter_rows() {
+            let line: String = row.iter().map(|&v| v.into()).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+abcd
+efgh
+ijkl
+";
+
+    #[test]
+    fn test_parse() {
+        let grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.rows(), 3);
+        assert_eq!(grid.cols(), 4);
+        assert_eq!(grid[(1, 2)], b'g');
+        assert_eq!(grid.to_string(), EXAMPLE);
+
+        let digits = Grid::parse("12\n34\n", |ch| ch.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits[(1, 0)], 3);
+
+        let err = "abc\nde\n".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(err, ParseGridError::Ragged(RaggedGridError { line: 2, expected: 3, found: 2 }));
+        let err = "abc\ndéf\n".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(err, ParseGridError::NotAscii { line: 2, col: 2, ch: 'é' });
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.get((2, 3)), Some(&b'l'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 4)), None);
+        *grid.get_mut((0, 0)).unwrap() = b'A';
+        grid[(0, 1)] = b'B';
+        assert_eq!(grid.row(0), b"ABcd");
+        assert_eq!(*grid.get_wrapping(-1, -1), b'l');
+        assert_eq!(*grid.get_wrapping(3, 5), b'B');
+    }
+
+    #[test]
+    #[should_panic(expected = "empty grid")]
+    fn test_get_wrapping_empty() {
+        Grid::<u8>::new(0, 0, b'.').get_wrapping(0, 0);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        let text = |line: &mut dyn Iterator<Item = Pos>| line.map(|pos| grid[pos] as char).collect::<String>();
+
+        let columns: Vec<String> = grid.iter_columns().map(|col| col.map(|&b| b as char).collect()).collect();
+        assert_eq!(columns, ["aei", "bfj", "cgk", "dhl"]);
+
+        let diagonals: Vec<String> = grid.diagonals().map(|mut d| text(&mut d)).collect();
+        assert_eq!(diagonals, ["i", "ej", "afk", "bgl", "ch", "d"]);
+
+        let anti: Vec<String> = grid.anti_diagonals().map(|mut d| text(&mut d)).collect();
+        assert_eq!(anti, ["a", "be", "cfi", "dgj", "hk", "l"]);
+
+        assert_eq!(text(&mut grid.line((2, 3), (-1, -1))), "lgb");
+        assert_eq!(grid.line((3, 0), (0, 1)).count(), 0);
+
+        let empty = Grid::new(3, 0, b'.');
+        assert_eq!(empty.diagonals().count(), 0);
+        assert_eq!(empty.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid: Grid<u8> = "#.#\n.#.\n".parse().unwrap();
+        assert_eq!(grid.find(&b'.'), Some((0, 1)));
+        assert_eq!(grid.find_all(&b'#').collect::<Vec<_>>(), [(0, 0), (0, 2), (1, 1)]);
+        assert_eq!(grid.find(&b'x'), None);
+    }
+
+    #[test]
+    fn test_with_border() {
+        let grid: Grid<u8> = "ab\ncd\n".parse().unwrap();
+        let bordered = grid.with_border(b'#');
+        assert_eq!(bordered.to_string(), "####\n#ab#\n#cd#\n####\n");
+        assert_eq!(bordered[(1, 1)], grid[(0, 0)]);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid: Grid<u8> = "#.\n.#\n".parse().unwrap();
+        let walls = grid.map(|&b| b == b'#');
+        assert_eq!(walls.find_all(&true).count(), 2);
+        let redrawn = walls.map(|&wall| if wall { 'X' } else { ' ' });
+        assert_eq!(redrawn.to_string(), "X \n X\n");
+    }
+
+    #[test]
+    fn test_parse_checked() {
+        let grid = Grid::parse_checked("#.\n.#\n", |ch| ".#".contains(ch)).unwrap();
+        assert_eq!(grid.rows(), 2);
+        let err = Grid::parse_checked("#.\n.x\n", |ch| ".#".contains(ch)).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: unexpected 'x'");
+        let err = Grid::parse_checked("#.\n.\n", |ch| ".#".contains(ch)).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected 2 columns, found 1");
+        assert_eq!(Grid::parse_checked("", |_| true), Err(AocError::new("empty grid")));
+    }
+}
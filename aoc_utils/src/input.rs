@@ -0,0 +1,122 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+//
+// Puzzle inputs are personal, so they aren't checked in.  We look for the
+// input for a given day in the following places, in order:
+//
+//  * The file named by the environment variable AOC_DAYnn_INPUT
+//  * dayNN.txt in the directory named by the environment variable AOC_INPUT_DIR
+//  * dayNN/input.txt in the workspace (where each day's input has always lived)
+//  * dayNN/input.txt in the `web` submodule
+//
+pub fn input_path(day: u32) -> Result<PathBuf, InputError> {
+    let var = format!("AOC_DAY{day:02}_INPUT");
+    if let Some(path) = std::env::var_os(&var) {
+        let path = PathBuf::from(path);
+        return if path.is_file() {
+            Ok(path)
+        } else {
+            Err(InputError::NotFound { day, searched: vec![path] })
+        };
+    }
+
+    let mut candidates = Vec::new();
+    if let Some(dir) = std::env::var_os("AOC_INPUT_DIR") {
+        candidates.push(Path::new(&dir).join(format!("day{day:02}.txt")));
+    }
+    let workspace = workspace_dir();
+    candidates.push(workspace.join(format!("day{day:02}/input.txt")));
+    candidates.push(workspace.join(format!("web/day{day:02}/input.txt")));
+
+    match candidates.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(InputError::NotFound { day, searched: candidates }),
+    }
+}
+
+pub fn load_input(day: u32) -> Result<String, InputError> {
+    let path = input_path(day)?;
+    std::fs::read_to_string(&path).map_err(|source| InputError::Io { path, source })
+}
+
+//
+// For a day's `main`: there's nothing useful to do without the input.
+//
+pub fn load_input_or_exit(day: u32) -> String {
+    load_input(day).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    })
+}
+
+//
+// For the `*_full` tests: the day's input, or return from the test
+// (so that it passes) if the input isn't available.
+//
+#[macro_export]
+macro_rules! full_input {
+    ($day:expr) => {
+        match $crate::load_input($day) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("skipping: {err}");
+                return;
+            }
+        }
+    };
+}
+
+fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("workspace").to_path_buf()
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound { day: u32, searched: Vec<PathBuf> },
+    Io { path: PathBuf, source: std::io::Error },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound { day, searched } => {
+                write!(f, "no input for day {day}; looked in:")?;
+                for path in searched {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Io { path, source } => write!(f, "unable to read {}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::NotFound { .. } => None,
+            InputError::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+#[test]
+fn test_env_var_overrides() {
+    let path = std::env::temp_dir().join(format!("aoc_utils_input_{}.txt", std::process::id()));
+    std::fs::write(&path, "1 2 3\n").unwrap();
+    std::env::set_var("AOC_DAY99_INPUT", &path);
+    assert_eq!(input_path(99).unwrap(), path);
+    assert_eq!(load_input(99).unwrap(), "1 2 3\n");
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(load_input(99), Err(InputError::NotFound { day: 99, .. })));
+    std::env::remove_var("AOC_DAY99_INPUT");
+}
+
+#[test]
+fn test_missing_input() {
+    let err = load_input(98).unwrap_err();
+    let message = err.to_string();
+    assert!(message.starts_with("no input for day 98"));
+    assert!(message.contains("day98/input.txt"));
+}
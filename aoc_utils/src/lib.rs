@@ -1,5 +1,22 @@
 use std::collections::{HashMap,HashSet};
 
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod differential;
+mod error;
+#[cfg(feature = "generate")]
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+mod input;
+mod solution;
+pub use differential::{assert_variants_agree, compare_variants, Disagreement, Outcome};
+pub use error::{AocError, LineContext, NomResultExt};
+pub use geometry::{Direction, Direction8, Point};
+pub use grid::Grid;
+pub use input::{input_path, load_input, load_input_or_exit, InputError};
+pub use solution::{erase, DynSolution, Erased, Registry, Solution, Solver, Variant, VariantId};
+
 pub trait HashPop<T> {
     fn pop(&mut self) -> Option<T>;
 }
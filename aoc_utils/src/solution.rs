@@ -0,0 +1,202 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::AocError;
+
+//
+// One named implementation of one part of a puzzle.  Most implementations
+// panic on malformed input; the `try_` entry points report it instead.
+//
+pub struct Variant<A> {
+    pub name: &'static str,
+    pub solve: Solver<A>,
+}
+
+pub enum Solver<A> {
+    Infallible(fn(&str) -> A),
+    Fallible(fn(&str) -> Result<A, AocError>),
+}
+
+impl<A> Variant<A> {
+    pub const fn new(name: &'static str, solve: fn(&str) -> A) -> Self {
+        Variant { name, solve: Solver::Infallible(solve) }
+    }
+
+    pub const fn fallible(name: &'static str, solve: fn(&str) -> Result<A, AocError>) -> Self {
+        Variant { name, solve: Solver::Fallible(solve) }
+    }
+
+    pub fn run(&self, input: &str) -> Result<A, AocError> {
+        match self.solve {
+            Solver::Infallible(solve) => Ok(solve(input)),
+            Solver::Fallible(solve) => solve(input),
+        }
+    }
+}
+
+//
+// A day's puzzle: both parts, each with one or more implementations.
+// The first variant of each part is the default (the one that day's
+// own `main` uses).  Every variant of a part must produce the same answer.
+//
+pub trait Solution {
+    const DAY: u32;
+    type Answer1: Display + 'static;
+    type Answer2: Display + 'static;
+    const PART1: &'static [Variant<Self::Answer1>];
+    const PART2: &'static [Variant<Self::Answer2>];
+}
+
+//
+// A `Solution` with its answer types erased, so that all of the days
+// can be kept together in one `Registry`.
+//
+pub trait DynSolution: Sync {
+    fn day(&self) -> u32;
+    fn variants(&self, part: u32) -> Vec<&'static str>;
+    fn solve(&self, part: u32, variant: &str, input: &str) -> Option<Result<String, AocError>>;
+}
+
+pub struct Erased<S>(PhantomData<S>);
+
+pub const fn erase<S: Solution>() -> Erased<S> {
+    Erased(PhantomData)
+}
+
+fn solve_with<A: Display>(variants: &[Variant<A>], name: &str, input: &str) -> Option<Result<String, AocError>> {
+    let variant = variants.iter().find(|v| v.name == name)?;
+    Some(variant.run(input).map(|answer| answer.to_string()))
+}
+
+impl<S: Solution + Sync> DynSolution for Erased<S> {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn variants(&self, part: u32) -> Vec<&'static str> {
+        match part {
+            1 => S::PART1.iter().map(|v| v.name).collect(),
+            2 => S::PART2.iter().map(|v| v.name).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn solve(&self, part: u32, variant: &str, input: &str) -> Option<Result<String, AocError>> {
+        match part {
+            1 => solve_with(S::PART1, variant, input),
+            2 => solve_with(S::PART2, variant, input),
+            _ => None,
+        }
+    }
+}
+
+//
+// Identifies a single implementation, e.g. "day 23, part 2, variant greedy".
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantId {
+    pub day: u32,
+    pub part: u32,
+    pub variant: &'static str,
+}
+
+impl Display for VariantId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {} part {} ({})", self.day, self.part, self.variant)
+    }
+}
+
+pub struct Registry {
+    days: &'static [&'static dyn DynSolution],
+}
+
+impl Registry {
+    pub const fn new(days: &'static [&'static dyn DynSolution]) -> Self {
+        Registry { days }
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = &'static dyn DynSolution> {
+        self.days.iter().copied()
+    }
+
+    pub fn day(&self, day: u32) -> Option<&'static dyn DynSolution> {
+        self.days().find(|d| d.day() == day)
+    }
+
+    //
+    // Every implementation of every part of every day.
+    //
+    pub fn variants(&self) -> impl Iterator<Item = VariantId> + '_ {
+        self.days().flat_map(|d| {
+            [1, 2].into_iter().flat_map(move |part| {
+                d.variants(part).into_iter().map(move |variant| VariantId { day: d.day(), part, variant })
+            })
+        })
+    }
+
+    //
+    // The implementation of the given day and part with the given name,
+    // or the default implementation if `variant` is None.
+    //
+    pub fn find(&self, day: u32, part: u32, variant: Option<&str>) -> Option<VariantId> {
+        let names = self.day(day)?.variants(part);
+        let variant = match variant {
+            Some(name) => names.into_iter().find(|&v| v == name)?,
+            None => *names.first()?,
+        };
+        Some(VariantId { day, part, variant })
+    }
+
+    pub fn solve(&self, id: VariantId, input: &str) -> Option<Result<String, AocError>> {
+        self.day(id.day)?.solve(id.part, id.variant, input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Example;
+    impl Solution for Example {
+        const DAY: u32 = 7;
+        type Answer1 = usize;
+        type Answer2 = String;
+        const PART1: &'static [Variant<usize>] = &[
+            Variant::new("default", |input| input.len()),
+            Variant::new("lines", |input| input.lines().map(|line| line.len() + 1).sum()),
+        ];
+        const PART2: &'static [Variant<String>] = &[
+            Variant::fallible("default", |input| match input.is_ascii() {
+                true => Ok(input.to_uppercase()),
+                false => Err(AocError::new("not ASCII")),
+            }),
+        ];
+    }
+
+    static REGISTRY: Registry = Registry::new(&[&erase::<Example>()]);
+
+    #[test]
+    fn test_find() {
+        let id = REGISTRY.find(7, 1, None).unwrap();
+        assert_eq!(id.variant, "default");
+        let id = REGISTRY.find(7, 1, Some("lines")).unwrap();
+        assert_eq!(id.to_string(), "day 7 part 1 (lines)");
+        assert!(REGISTRY.find(7, 2, Some("lines")).is_none());
+        assert!(REGISTRY.find(8, 1, None).is_none());
+    }
+
+    #[test]
+    fn test_variants() {
+        let names: Vec<_> = REGISTRY.variants().map(|id| (id.part, id.variant)).collect();
+        assert_eq!(names, [(1, "default"), (1, "lines"), (2, "default")]);
+    }
+
+    #[test]
+    fn test_solve() {
+        let id = REGISTRY.find(7, 1, Some("lines")).unwrap();
+        assert_eq!(REGISTRY.solve(id, "ab\ncd\n").unwrap(), Ok("6".to_string()));
+        let id = REGISTRY.find(7, 2, None).unwrap();
+        assert_eq!(REGISTRY.solve(id, "ab").unwrap(), Ok("AB".to_string()));
+        assert_eq!(REGISTRY.solve(id, "ä").unwrap(), Err(AocError::new("not ASCII")));
+    }
+}
@@ -0,0 +1,42 @@
+//
+// Sorting dominates distance, so distance_radix (a counting or radix
+// sort) should pull ahead of distance as the lists get longer.
+//
+use aoc_utils::bench::generated;
+use divan::Bencher;
+use day01::LocationLists;
+
+fn main() {
+    divan::main();
+}
+
+// Lines (the real input has 1000)
+const SIZES: &[usize] = &[1000, 10_000, 100_000, 1_000_000];
+
+fn lists(size: usize) -> LocationLists {
+    generated(1, size).parse().expect("valid input")
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_parse(bencher: Bencher, size: usize) {
+    let input = generated(1, size);
+    bencher.bench(|| input.parse::<LocationLists>());
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_distance(bencher: Bencher, size: usize) {
+    let lists = lists(size);
+    bencher.bench(|| lists.distance());
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_distance_radix(bencher: Bencher, size: usize) {
+    let lists = lists(size);
+    bencher.bench(|| lists.distance_radix());
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_similarity(bencher: Bencher, size: usize) {
+    let lists = lists(size);
+    bencher.bench(|| lists.similarity());
+}
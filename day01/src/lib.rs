@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use aoc_utils::{AocError, LineContext, Solution, Variant};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    type Answer1 = u64;
+    type Answer2 = u64;
+    const PART1: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part1),
+        Variant::fallible("radix", |input| Ok(input.parse::<LocationLists>()?.distance_radix())),
+    ];
+    const PART2: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+//
+// The two lists of location IDs, one pair per line, like "3   4".
+//
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocationLists {
+    pub left: Vec<u32>,
+    pub right: Vec<u32>,
+}
+
+//
+// Parse the pairs one line at a time, without collecting them.
+//
+pub fn pairs(input: &str) -> impl Iterator<Item = Result<(u32, u32), AocError>> + '_ {
+    input.lines().enumerate().map(|(i, line)| {
+        let mut words = line.split_whitespace();
+        let (Some(l), Some(r), None) = (words.next(), words.next(), words.next()) else {
+            return Err(AocError::at_line(i + 1, "expected two numbers"));
+        };
+        Ok((l.parse().on_line(i + 1)?, r.parse().on_line(i + 1)?))
+    })
+}
+
+impl FromStr for LocationLists {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, AocError> {
+        pairs(input).collect()
+    }
+}
+
+impl FromIterator<(u32, u32)> for LocationLists {
+    fn from_iter<I: IntoIterator<Item = (u32, u32)>>(iter: I) -> Self {
+        let (left, right) = iter.into_iter().unzip();
+        LocationLists { left, right }
+    }
+}
+
+impl LocationLists {
+    //
+    // Pair up the smallest of each list, then the next smallest, and so
+    // on, and add up how far apart each pair is.
+    //
+    pub fn distance(&self) -> u64 {
+        let mut left = self.left.clone();
+        let mut right = self.right.clone();
+        left.sort_unstable();
+        right.sort_unstable();
+        total_distance(&left, &right)
+    }
+
+    //
+    // Same as `distance`, but sorts in linear time: a counting sort if
+    // the IDs are close together compared to the number of them, or a
+    // radix sort otherwise.
+    //
+    pub fn distance_radix(&self) -> u64 {
+        total_distance(&sort_bounded(&self.left), &sort_bounded(&self.right))
+    }
+
+    //
+    // Each number in the left list, times the number of times it appears
+    // in the right list.
+    //
+    pub fn similarity(&self) -> u64 {
+        let mut counts = HashMap::<u32, u64>::new();
+        for &r in &self.right {
+            *counts.entry(r).or_insert(0) += 1;
+        }
+        self.left.iter().map(|l| *l as u64 * counts.get(l).unwrap_or(&0)).sum()
+    }
+}
+
+fn total_distance(left: &[u32], right: &[u32]) -> u64 {
+    left.iter().zip(right).map(|(l, r)| l.abs_diff(*r) as u64).sum()
+}
+
+fn sort_bounded(values: &[u32]) -> Vec<u32> {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return Vec::new();
+    };
+    let range = (max - min) as usize + 1;
+    if range <= 4 * values.len() {
+        let mut counts = vec![0usize; range];
+        for &value in values {
+            counts[(value - min) as usize] += 1;
+        }
+        let mut sorted = Vec::with_capacity(values.len());
+        for (offset, &count) in counts.iter().enumerate() {
+            sorted.extend(std::iter::repeat_n(min + offset as u32, count));
+        }
+        sorted
+    } else {
+        radix_sort(values)
+    }
+}
+
+//
+// Least significant digit first, one byte at a time.  Passes where
+// every value has the same byte are skipped.
+//
+fn radix_sort(values: &[u32]) -> Vec<u32> {
+    let mut sorted = values.to_vec();
+    let mut scratch = vec![0; values.len()];
+    for shift in [0, 8, 16, 24] {
+        let mut offsets = [0usize; 257];
+        for &value in &sorted {
+            offsets[((value >> shift) & 0xFF) as usize + 1] += 1;
+        }
+        if offsets.contains(&sorted.len()) {
+            continue;
+        }
+        for digit in 0..256 {
+            offsets[digit + 1] += offsets[digit];
+        }
+        for &value in &sorted {
+            let digit = ((value >> shift) & 0xFF) as usize;
+            scratch[offsets[digit]] = value;
+            offsets[digit] += 1;
+        }
+        std::mem::swap(&mut sorted, &mut scratch);
+    }
+    sorted
+}
+
+pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str) -> Result<u64, AocError> {
+    Ok(input.parse::<LocationLists>()?.distance())
+}
+
+pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part2(input: &str) -> Result<u64, AocError> {
+    Ok(input.parse::<LocationLists>()?.similarity())
+}
+
+#[test]
+fn test_part1() {
+    let input = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+    assert_eq!(part1(input), 11);
+}
+
+#[test]
+fn test_part2() {
+    let input = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+    assert_eq!(part2(input), 31);
+}
+
+#[test]
+fn test_location_lists() {
+    let lists: LocationLists = "3   4\n4   3\n2   5\n".parse().unwrap();
+    assert_eq!(lists, LocationLists { left: vec![3, 4, 2], right: vec![4, 3, 5] });
+    assert_eq!(lists.distance(), 3);
+    assert_eq!(lists.distance_radix(), 3);
+    assert_eq!(lists.similarity(), 7);
+    assert_eq!("".parse::<LocationLists>(), Ok(LocationLists::default()));
+}
+
+#[test]
+fn test_sort_bounded() {
+    // A counting sort
+    assert_eq!(sort_bounded(&[5, 3, 4, 3]), vec![3, 3, 4, 5]);
+    // A radix sort
+    let values = [70000, 12, u32::MAX, 256, 65535, 0, 12];
+    let mut expected = values.to_vec();
+    expected.sort();
+    assert_eq!(sort_bounded(&values), expected);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("3   4\n4\n").unwrap_err().to_string(), "line 2: expected two numbers");
+    assert_eq!(try_part1("3   4 5\n").unwrap_err().to_string(), "line 1: expected two numbers");
+    assert_eq!(try_part2("3   4\n4  x\n").unwrap_err().to_string(), "line 2: invalid number: invalid digit found in string");
+    assert_eq!(try_part2("-3   4\n").unwrap_err().to_string(), "line 1: invalid number: invalid digit found in string");
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    aoc_utils::assert_variants_agree::<Day01>(input);
+}
@@ -0,0 +1,443 @@
+use std::cmp::{Ordering, Reverse};
+use std::fmt::Display;
+
+use aoc_utils::{AocError, LineContext, Solution, Variant};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+        Variant::fallible("dp", |input| count_safe(input, 0)),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part2),
+        Variant::fallible("dp", |input| count_safe(input, 1)),
+    ];
+}
+
+fn parse_report(report: &str) -> Result<Vec<i32>, AocError> {
+    let levels = report.split_whitespace().map(|word| word.parse()).collect::<Result<Vec<i32>, _>>()?;
+    if levels.len() < 2 {
+        return Err(AocError::new("a report needs at least two levels"));
+    }
+    Ok(levels)
+}
+
+fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, AocError> {
+    input.lines().enumerate().map(|(i, line)| parse_report(line).on_line(i + 1)).collect()
+}
+
+fn report_is_safe(levels: &[i32]) -> bool {
+    let mut numbers = levels.iter().copied();
+    let first = numbers.next().expect("number");
+    let mut last = numbers.next().expect("number");
+    let sign = (last - first).signum();
+    if sign == 0 || (last - first).unsigned_abs() > StepRange::PUZZLE.max {
+        return false;
+    }
+
+    for n in numbers {
+        if (n - last).signum() != sign || (n - last).unsigned_abs() > StepRange::PUZZLE.max {
+            return false;
+        }
+        last = n;
+    }
+
+    true
+}
+
+pub fn part1(input: &str) -> usize {
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    Ok(parse_reports(input)?.iter().filter(|levels| report_is_safe(levels)).count())
+}
+
+fn find_bad_level(levels: &[i32]) -> Option<usize> {
+    let sign = (levels[1] - levels[0]).signum();
+    if sign == 0 {
+        return Some(1)
+    }
+    for i in 1..levels.len() {
+        if (levels[i] - levels[i-1]).signum() != sign || (levels[i] - levels[i-1]).unsigned_abs() > StepRange::PUZZLE.max {
+            return Some(i)
+        }
+    }
+    None
+}
+
+//
+// If we find a level that is inconsistent (according to the rules for part 1),
+// we need to try again after removing either that level, or the preceding one.
+//
+fn report_is_safe2(levels: &[i32]) -> bool {
+    let mut levels = levels.to_vec();
+    if let Some(i) = find_bad_level(&levels) {
+        // Try removing levels[0]
+        if find_bad_level(&levels[1..]).is_none() {
+            return true;
+        }
+        // Try removing levels[i]
+        let misfit = levels.remove(i);
+        if find_bad_level(&levels).is_none() {
+            return true;
+        }
+        // Try removing levels[i-1]
+        levels.insert(i, misfit);
+        levels.remove(i-1);
+        find_bad_level(&levels).is_none()
+    } else {
+        true
+    }
+}
+
+//
+// How much adjacent levels may differ in a safe report.  The levels must
+// also all increase or all decrease.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl StepRange {
+    pub const PUZZLE: StepRange = StepRange { min: 1, max: 3 };
+
+    // With a minimum of 0, equal levels are allowed in either direction
+    fn allows(&self, from: i32, to: i32, increasing: bool) -> bool {
+        (to == from || (to > from) == increasing) && (self.min..=self.max).contains(&from.abs_diff(to))
+    }
+}
+
+//
+// Find the fewest levels to remove (at most `max_removals`) so that the
+// rest of the report is safe, returning their indices in increasing order.
+// Returns None if it takes more than `max_removals`.  A single remaining
+// level counts as safe.
+//
+// For each direction, best[i] is the fewest removals from levels[..i] so
+// that a safe report ends by keeping levels[i].  The previous kept level
+// can't be more than max_removals + 1 back, so this takes O(n * K) time.
+// When there's a choice, the later levels are kept (as in the puzzle's
+// examples, where "1 3 2 4 5" is safe by removing the 3).
+//
+pub fn levels_to_remove(levels: &[i32], steps: StepRange, max_removals: usize) -> Option<Vec<usize>> {
+    let n = levels.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+
+    let mut answer: Option<(usize, Vec<usize>)> = None;
+    for increasing in [true, false] {
+        // (removals, previous kept index) for each kept level
+        let mut best: Vec<(usize, Option<usize>)> = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut entry = (i, None);
+            for j in i.saturating_sub(max_removals + 1)..i {
+                let removals = best[j].0 + (i - j - 1);
+                if removals <= entry.0 && steps.allows(levels[j], levels[i], increasing) {
+                    entry = (removals, Some(j));
+                }
+            }
+            best.push(entry);
+        }
+
+        let Some((removals, last)) = (0..n).map(|i| (best[i].0 + (n - 1 - i), i)).min_by_key(|&(removals, i)| (removals, Reverse(i))) else {
+            continue;
+        };
+        if removals > max_removals || answer.as_ref().is_some_and(|(fewest, _)| *fewest <= removals) {
+            continue;
+        }
+        let mut kept = vec![false; n];
+        let mut index = Some(last);
+        while let Some(i) = index {
+            kept[i] = true;
+            index = best[i].1;
+        }
+        answer = Some((removals, (0..n).filter(|&i| !kept[i]).collect()));
+    }
+    answer.map(|(_, removed)| removed)
+}
+
+//
+// How many reports can be made safe by removing at most `max_removals`
+// levels.
+//
+pub fn count_safe(input: &str, max_removals: usize) -> Result<usize, AocError> {
+    Ok(parse_reports(input)?.iter()
+        .filter(|levels| levels_to_remove(levels, StepRange::PUZZLE, max_removals).is_some())
+        .count())
+}
+
+pub fn part2(input: &str) -> usize {
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    Ok(parse_reports(input)?.iter().filter(|report| report_is_safe2(report)).count())
+}
+
+//
+// An explanation of why a report is (or isn't) safe, for checking
+// results by hand.
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub levels: Vec<i32>,
+    // From the first two levels; None if they are equal
+    pub trend: Option<Trend>,
+    // The first level that breaks the rules, and which rule
+    pub violation: Option<(usize, Violation)>,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Increasing,
+    Decreasing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    ZeroStep,
+    WrongSign,
+    TooLarge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    // Safe once the level at this index is removed
+    Dampened(usize),
+    Unsafe,
+}
+
+impl Display for Trend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Trend::Increasing => "increasing",
+            Trend::Decreasing => "decreasing",
+        })
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Violation::ZeroStep => "zero step",
+            Violation::WrongSign => "wrong sign",
+            Violation::TooLarge => "too large",
+        })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Safe => f.write_str("safe"),
+            Verdict::Dampened(index) => write!(f, "safe without {index}"),
+            Verdict::Unsafe => f.write_str("unsafe"),
+        }
+    }
+}
+
+impl Diagnostic {
+    // `levels` must have at least two levels, which `diagnose` checks
+    fn new(line: usize, levels: Vec<i32>) -> Diagnostic {
+        let trend = match levels[1].cmp(&levels[0]) {
+            Ordering::Greater => Some(Trend::Increasing),
+            Ordering::Less => Some(Trend::Decreasing),
+            Ordering::Equal => None,
+        };
+        let violation = (1..levels.len()).find_map(|i| {
+            let step = levels[i] - levels[i - 1];
+            let rule = if step == 0 {
+                Violation::ZeroStep
+            } else if (step > 0) != (trend == Some(Trend::Increasing)) {
+                Violation::WrongSign
+            } else if step.unsigned_abs() > StepRange::PUZZLE.max {
+                Violation::TooLarge
+            } else {
+                return None;
+            };
+            Some((i, rule))
+        });
+        let verdict = match (violation, levels_to_remove(&levels, StepRange::PUZZLE, 1).as_deref()) {
+            (None, _) => Verdict::Safe,
+            (Some(_), Some(&[index])) => Verdict::Dampened(index),
+            _ => Verdict::Unsafe,
+        };
+        Diagnostic { line, levels, trend, violation, verdict }
+    }
+
+    pub fn to_json(&self) -> String {
+        let levels: Vec<String> = self.levels.iter().map(|level| level.to_string()).collect();
+        let string = |value: Option<String>| value.map_or("null".to_string(), |value| format!("\"{value}\""));
+        let number = |value: Option<usize>| value.map_or("null".to_string(), |value| value.to_string());
+        format!(
+            r#"{{"line": {}, "levels": [{}], "trend": {}, "first_bad_index": {}, "rule": {}, "safe": {}, "removal": {}}}"#,
+            self.line,
+            levels.join(", "),
+            string(self.trend.map(|trend| trend.to_string())),
+            number(self.violation.map(|(index, _)| index)),
+            string(self.violation.map(|(_, rule)| rule.to_string())),
+            self.verdict != Verdict::Unsafe,
+            number(match self.verdict {
+                Verdict::Dampened(index) => Some(index),
+                _ => None,
+            }),
+        )
+    }
+}
+
+//
+// Diagnose each report in turn, as it is parsed.
+//
+pub fn diagnose(input: &str) -> impl Iterator<Item = Result<Diagnostic, AocError>> + '_ {
+    input.lines().enumerate().map(|(i, line)| {
+        Ok(Diagnostic::new(i + 1, parse_report(line).on_line(i + 1)?))
+    })
+}
+
+//
+// One row per report:
+//
+//      line  verdict          trend       bad  rule        levels
+//         1  safe             decreasing                   7 6 4 2 1
+//         4  safe without 1   increasing    2  wrong sign  1 3 2 4 5
+//
+pub fn diagnostics_table(diagnostics: &[Diagnostic]) -> String {
+    let mut table = format!("{:>4}  {:<15}  {:<10}  {:>3}  {:<10}  levels\n", "line", "verdict", "trend", "bad", "rule");
+    for diagnostic in diagnostics {
+        let trend = diagnostic.trend.map(|trend| trend.to_string()).unwrap_or_default();
+        let (bad, rule) = match diagnostic.violation {
+            Some((index, rule)) => (index.to_string(), rule.to_string()),
+            None => (String::new(), String::new()),
+        };
+        let levels: Vec<String> = diagnostic.levels.iter().map(|level| level.to_string()).collect();
+        let row = format!("{:>4}  {:<15}  {trend:<10}  {bad:>3}  {rule:<10}  {}",
+            diagnostic.line, diagnostic.verdict.to_string(), levels.join(" "));
+        table += row.trim_end();
+        table += "\n";
+    }
+    table
+}
+
+//
+// A JSON array, with one object per report.
+//
+pub fn diagnostics_json(diagnostics: &[Diagnostic]) -> String {
+    let objects: Vec<String> = diagnostics.iter().map(|diagnostic| format!("  {}", diagnostic.to_json())).collect();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+#[test]
+fn test_part1() {
+    let input = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+    assert_eq!(part1(input), 2);
+}
+
+#[test]
+fn test_part2() {
+    let input = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+    assert_eq!(part2(input), 4);
+}
+
+#[test]
+#[allow(clippy::bool_assert_comparison)]
+fn test_report_is_safe2() {
+    let report_is_safe2 = |report| report_is_safe2(&parse_report(report).unwrap());
+    assert_eq!(report_is_safe2("7 6 4 2 1"), true);
+    assert_eq!(report_is_safe2("1 2 7 8 9"), false);
+    assert_eq!(report_is_safe2("9 7 6 2 1"), false);
+    assert_eq!(report_is_safe2("1 3 2 4 5"), true);     // Remove "3"
+    assert_eq!(report_is_safe2("8 6 4 4 1"), true);     // Remove either "4"
+    assert_eq!(report_is_safe2("1 3 6 7 9"), true);
+    assert_eq!(report_is_safe2("1 2 9 3 4"), true);     // Remove "9"
+    assert_eq!(report_is_safe2("76 77 79 82 84 87 89 95"), true);   // Remove "95"
+    assert_eq!(report_is_safe2("41 38 40 42 44 47"), true);     // Remove "41"
+}
+
+#[test]
+fn test_levels_to_remove() {
+    let remove = |report, max_removals| levels_to_remove(&parse_report(report).unwrap(), StepRange::PUZZLE, max_removals);
+    assert_eq!(remove("7 6 4 2 1", 0), Some(vec![]));
+    assert_eq!(remove("1 2 7 8 9", 1), None);
+    assert_eq!(remove("1 2 9 3 4", 0), None);
+    assert_eq!(remove("1 2 9 3 4", 1), Some(vec![2]));
+    assert_eq!(remove("41 38 40 42 44 47", 1), Some(vec![0]));
+    assert_eq!(remove("1 2 9 10 3 4", 1), None);
+    assert_eq!(remove("1 2 9 10 3 4", 2), Some(vec![2, 3]));
+    // Either 9 8 7 6, or 1 2 3 6
+    assert_eq!(remove("9 1 8 2 7 3 6", 2), None);
+    assert_eq!(remove("9 1 8 2 7 3 6", 3).map(|removed| removed.len()), Some(3));
+    assert_eq!(remove("5 5 5 5", 2), None);
+    assert_eq!(remove("5 5 5 5", 3), Some(vec![0, 1, 2]));
+
+    let wide = StepRange { min: 0, max: 10 };
+    assert_eq!(levels_to_remove(&[1, 1, 8, 18], wide, 0), Some(vec![]));
+    assert_eq!(levels_to_remove(&[1, 1, 8, 19], wide, 0), None);
+}
+
+#[test]
+fn test_count_safe() {
+    let input = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+    assert_eq!(count_safe(input, 0), Ok(2));
+    assert_eq!(count_safe(input, 1), Ok(4));
+    assert_eq!(count_safe(input, 2), Ok(6));
+}
+
+#[test]
+fn test_diagnose() {
+    let input = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+    let diagnostics: Vec<Diagnostic> = diagnose(input).collect::<Result<_, _>>().unwrap();
+    let summary: Vec<_> = diagnostics.iter().map(|d| (d.trend, d.violation, d.verdict)).collect();
+    assert_eq!(summary, vec![
+        (Some(Trend::Decreasing), None, Verdict::Safe),
+        (Some(Trend::Increasing), Some((2, Violation::TooLarge)), Verdict::Unsafe),
+        (Some(Trend::Decreasing), Some((3, Violation::TooLarge)), Verdict::Unsafe),
+        (Some(Trend::Increasing), Some((2, Violation::WrongSign)), Verdict::Dampened(1)),
+        (Some(Trend::Decreasing), Some((3, Violation::ZeroStep)), Verdict::Dampened(2)),
+        (Some(Trend::Increasing), None, Verdict::Safe),
+    ]);
+    assert_eq!(Diagnostic::new(1, vec![3, 3, 4]).trend, None);
+    assert!(diagnose("1 2\n3\n").nth(1).unwrap().is_err());
+
+    assert_eq!(diagnostics_table(&diagnostics[3..5]), "\
+line  verdict          trend       bad  rule        levels
+   4  safe without 1   increasing    2  wrong sign  1 3 2 4 5
+   5  safe without 2   decreasing    3  zero step   8 6 4 4 1
+");
+    assert_eq!(diagnostics_json(&diagnostics[..2]), r#"[
+  {"line": 1, "levels": [7, 6, 4, 2, 1], "trend": "decreasing", "first_bad_index": null, "rule": null, "safe": true, "removal": null},
+  {"line": 2, "levels": [1, 2, 7, 8, 9], "trend": "increasing", "first_bad_index": 2, "rule": "too large", "safe": false, "removal": null}
+]
+"#);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("1 2 3\n4\n").unwrap_err().to_string(), "line 2: a report needs at least two levels");
+    assert_eq!(try_part2("1 2 3\n4 5 +\n").unwrap_err().to_string(), "line 2: invalid number: invalid digit found in string");
+}
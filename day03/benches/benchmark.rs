@@ -1,27 +1,141 @@
-use day03::{ part1, part1_many_till, part2, part2_state_machine };
-
+//
+// The candidate scans (memchr, swar) only differ in how they find where
+// an instruction might start, so the generated benches report bytes per
+// second, on inputs far bigger than the real one.
+//
 fn main() {
-    divan::main();
+    aoc_utils::bench::main(3);
 }
 
-const INPUT: &str = include_str!("../input.txt");
+mod full_input {
+    use std::sync::LazyLock;
 
-#[divan::bench]
-fn bench_part1() {
-    part1(INPUT);
-}
+    use aoc_utils::load_input;
+    use day03::{ part1, part1_many_till, part1_memchr, part1_state_machine, part1_swar, part2, part2_memchr, part2_state_machine, part2_swar, Interpreter };
 
-#[divan::bench]
-fn bench_part1_many_till() {
-    part1_many_till(INPUT);
-}
+    static INPUT: LazyLock<String> = LazyLock::new(|| load_input(3).expect("input"));
+
+    #[divan::bench]
+    fn bench_part1() {
+        part1(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part1_many_till() {
+        part1_many_till(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part1_state_machine() {
+        part1_state_machine(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part1_interpreter() {
+        Interpreter::part1().run(&INPUT).unwrap();
+    }
+
+    #[divan::bench]
+    fn bench_part1_memchr() {
+        part1_memchr(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part1_swar() {
+        part1_swar(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part2() {
+        part2(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part2_state_machine() {
+        part2_state_machine(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part2_interpreter() {
+        Interpreter::part2().run(&INPUT).unwrap();
+    }
+
+    #[divan::bench]
+    fn bench_part2_memchr() {
+        part2_memchr(&INPUT);
+    }
 
-#[divan::bench]
-fn bench_part2() {
-    part2(INPUT);
+    #[divan::bench]
+    fn bench_part2_swar() {
+        part2_swar(&INPUT);
+    }
 }
 
-#[divan::bench]
-fn bench_part2_state_machine() {
-    part2_state_machine(INPUT);
+mod generated {
+    use aoc_utils::bench::generated;
+    use divan::{Bencher, counter::BytesCount};
+    use day03::{ part1, part1_many_till, part1_memchr, part1_state_machine, part1_swar, part2, part2_memchr, part2_state_machine, part2_swar, Interpreter };
+
+    // Bytes of corrupted memory (the real input is about 18K)
+    const SIZES: &[usize] = &[1 << 20, 4 << 20, 16 << 20];
+
+    fn bench<T>(bencher: Bencher, size: usize, solve: fn(&str) -> T) {
+        let input = generated(3, size);
+        bencher.counter(BytesCount::of_str(&input)).bench(|| solve(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1(bencher: Bencher, size: usize) {
+        bench(bencher, size, part1);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1_many_till(bencher: Bencher, size: usize) {
+        bench(bencher, size, part1_many_till);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1_state_machine(bencher: Bencher, size: usize) {
+        bench(bencher, size, part1_state_machine);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1_interpreter(bencher: Bencher, size: usize) {
+        bench(bencher, size, |input| Interpreter::part1().run(input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1_memchr(bencher: Bencher, size: usize) {
+        bench(bencher, size, part1_memchr);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1_swar(bencher: Bencher, size: usize) {
+        bench(bencher, size, part1_swar);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2(bencher: Bencher, size: usize) {
+        bench(bencher, size, part2);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2_state_machine(bencher: Bencher, size: usize) {
+        bench(bencher, size, part2_state_machine);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2_interpreter(bencher: Bencher, size: usize) {
+        bench(bencher, size, |input| Interpreter::part2().run(input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2_memchr(bencher: Bencher, size: usize) {
+        bench(bencher, size, part2_memchr);
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2_swar(bencher: Bencher, size: usize) {
+        bench(bencher, size, part2_swar);
+    }
 }
@@ -1,6 +1,30 @@
-use nom::{branch::alt, bytes::complete::{tag, take, take_while_m_n}, character::complete::anychar, multi::{many0, many1, many_till}, sequence::{preceded, separated_pair, terminated}, IResult};
+use nom::{branch::alt, bytes::complete::{tag, take_while_m_n}, character::complete::anychar, combinator::consumed, multi::many_till, sequence::{preceded, separated_pair, terminated}, IResult};
 use nom::AsChar;
 use nom::Parser;
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    type Answer1 = u64;
+    type Answer2 = u64;
+    const PART1: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part1),
+        Variant::new("many_till", part1_many_till),
+        Variant::new("state_machine", part1_state_machine),
+        Variant::fallible("interpreter", part1_interpreter),
+        Variant::new("memchr", part1_memchr),
+        Variant::new("swar", part1_swar),
+    ];
+    const PART2: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part2),
+        Variant::new("state_machine", part2_state_machine),
+        Variant::fallible("interpreter", part2_interpreter),
+        Variant::new("memchr", part2_memchr),
+        Variant::new("swar", part2_swar),
+    ];
+}
 
 fn short_number(input: &str) -> IResult<&str, u32> {
     // TODO: It seems like there ought to be a way to do this without the closure
@@ -13,11 +37,6 @@ fn mul_instruction(input: &str) -> IResult<&str, (u32, u32)> {
     preceded(tag("mul("), terminated(separated_pair(short_number, tag(","), short_number), tag(")")))(input)
 }
 
-fn ignore_character(input: &str) -> IResult<&str, (u32, u32)> {
-    let (remaining, _ignored) = take(1u32)(input)?;
-    Ok((remaining, (0, 0)))
-}
-
 fn parse_do(input: &str) -> IResult<&str, ()> {
     let (remaining, _found) = tag("do()")(input)?;
     Ok((remaining, ()))
@@ -28,50 +47,133 @@ fn parse_dont(input: &str) -> IResult<&str, ()> {
     Ok((remaining, ()))
 }
 
-pub fn part1(input: &str) -> u32 {
-    let (_remaining, args) = many0(alt((mul_instruction, ignore_character)))(input).unwrap();
-    args.iter()
-        .map(|(a, b)| a*b)
-        .sum()
+//
+// The instructions that can be found in the corrupted memory.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
 }
 
-pub fn part1_many_till(input: &str) -> u32 {
-    let (_remaining, args) = many1(
-            many_till(anychar, mul_instruction)
-            .map(|(_chars, args)| args)
-        )(input)
-        .unwrap();
-    args.iter()
-        .map(|(a, b)| a*b)
-        .sum()
+//
+// An instruction, and the byte offset where it starts in the input.
+//
+pub type Token = (usize, Instruction);
+
+fn instruction(input: &str) -> IResult<&str, Instruction> {
+    alt((
+        mul_instruction.map(|(a, b)| Instruction::Mul(a, b)),
+        parse_do.map(|_| Instruction::Do),
+        parse_dont.map(|_| Instruction::Dont),
+    ))(input)
 }
 
-pub fn part2(input: &str) -> u32 {
-    let mut result = 0;
-    let mut remaining = input;
-    let mut enabled = true;
+//
+// Try to parse an instruction at each position in the input, skipping
+// a character at a time where there isn't one.
+//
+pub struct Tokens<'a> {
+    input: &'a str,
+    offset: usize,
+}
 
-    while !remaining.is_empty() {
-        if let Ok((r, (a,b))) = mul_instruction(remaining) {
-            remaining = r;
-            if enabled {
-                result += a * b;
+pub fn tokenize(input: &str) -> Tokens<'_> {
+    Tokens { input, offset: 0 }
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        while let Some(ch) = self.input[self.offset..].chars().next() {
+            let start = self.offset;
+            if let Ok((remaining, instruction)) = instruction(&self.input[start..]) {
+                self.offset = self.input.len() - remaining.len();
+                return Some((start, instruction));
             }
-        } else if let Ok((r, _ignored)) = parse_do(remaining) {
-            remaining = r;
-            enabled = true;
-        } else if let Ok((r, _ignored)) = parse_dont(remaining) {
-            remaining = r;
-            enabled = false;
-        } else {
-            remaining = &remaining[1..];
+            self.offset += ch.len_utf8();
         }
+        None
     }
+}
+
+//
+// Part 1: the sum of every mul(), ignoring do() and don't().
+//
+pub fn sum_products(tokens: impl IntoIterator<Item = Token>) -> u64 {
+    tokens.into_iter()
+        .map(|(_, instruction)| match instruction {
+            Instruction::Mul(a, b) => a as u64 * b as u64,
+            _ => 0,
+        })
+        .sum()
+}
+
+//
+// Part 2: the sum of the mul()s that aren't turned off by a don't().
+//
+pub fn sum_enabled(tokens: impl IntoIterator<Item = Token>) -> u64 {
+    let (sum, _enabled) = tokens.into_iter().fold((0, true), |(sum, enabled), (_, instruction)| {
+        match instruction {
+            Instruction::Mul(a, b) if enabled => (sum + a as u64 * b as u64, enabled),
+            Instruction::Mul(..) => (sum, enabled),
+            Instruction::Do => (sum, true),
+            Instruction::Dont => (sum, false),
+        }
+    });
+    sum
+}
 
-    result
+//
+// Corrupted memory can contain anything, so there's no such thing as
+// invalid input.
+//
+pub fn try_part1(input: &str) -> Result<u64, AocError> {
+    Ok(part1(input))
 }
 
-pub fn part2_state_machine(input: &str) -> u32 {
+pub fn try_part2(input: &str) -> Result<u64, AocError> {
+    Ok(part2(input))
+}
+
+pub fn part1(input: &str) -> u64 {
+    sum_products(tokenize(input))
+}
+
+pub fn part2(input: &str) -> u64 {
+    sum_enabled(tokenize(input))
+}
+
+//
+// Only finds mul() instructions, by skipping characters until one
+// matches.
+//
+pub fn tokenize_many_till(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut remaining = input;
+    while let Ok((rest, (_skipped, (text, (a, b))))) = many_till(anychar, consumed(mul_instruction))(remaining) {
+        let start = input.len() - rest.len() - text.len();
+        tokens.push((start, Instruction::Mul(a, b)));
+        remaining = rest;
+    }
+    tokens
+}
+
+pub fn part1_many_till(input: &str) -> u64 {
+    sum_products(tokenize_many_till(input))
+}
+
+pub fn part1_state_machine(input: &str) -> u64 {
+    sum_products(tokenize_state_machine(input))
+}
+
+pub fn part2_state_machine(input: &str) -> u64 {
+    sum_enabled(tokenize_state_machine(input))
+}
+
+pub fn tokenize_state_machine(input: &str) -> Vec<Token> {
     enum States {
         Looking,    // Not inside any function
         Mul1,       // Saw "m"
@@ -95,60 +197,343 @@ pub fn part2_state_machine(input: &str) -> u32 {
         state: States,
         arg0: u32,      // First argument to mul()
         arg1: u32,      // Second argument to mul()
-        enabled: bool,  // If true, process mul() instructions
-        sum: u32        // The sum of all enabled mul() instructions
+        digits: u32,    // Digits seen so far in the current argument
+        start: usize,   // Offset of the "m" or "d" that started this instruction
     }
 
     let mut state = State {
         state: Looking,
-        arg0: 0, arg1: 0,
-        enabled: true,
-        sum: 0
+        arg0: 0, arg1: 0, digits: 0,
+        start: 0,
     };
+    let mut tokens = Vec::new();
 
-    for ch in input.chars() {
+    for (offset, ch) in input.char_indices() {
         state.state = match (state.state, ch) {
-            (Looking, 'm') => Mul1,
             (Mul1, 'u') => Mul2,
             (Mul2, 'l') => Mul3,
-            (Mul3, '(') => { state.arg0 = 0; Mul4 }
-            (Mul4, digit) if digit.is_ascii_digit() => {
-                state.arg0 = state.arg0 * 10 + (digit as u32 - '0' as u32);
-                Mul5
-            }
-            (Mul5, digit) if digit.is_ascii_digit() => {
+            (Mul3, '(') => { state.arg0 = 0; state.digits = 0; Mul4 }
+            (Mul4 | Mul5, digit) if digit.is_ascii_digit() && state.digits < 3 => {
                 state.arg0 = state.arg0 * 10 + (digit as u32 - '0' as u32);
+                state.digits += 1;
                 Mul5
             }
-            (Mul5, ',') => { state.arg1 = 0; Mul6 }
-            (Mul6, digit) if digit.is_ascii_digit() => {
-                state.arg1 = state.arg1 * 10 + (digit as u32 - '0' as u32);
-                Mul7
-            }
-            (Mul7, digit) if digit.is_ascii_digit() => {
+            (Mul5, ',') => { state.arg1 = 0; state.digits = 0; Mul6 }
+            (Mul6 | Mul7, digit) if digit.is_ascii_digit() && state.digits < 3 => {
                 state.arg1 = state.arg1 * 10 + (digit as u32 - '0' as u32);
+                state.digits += 1;
                 Mul7
             }
             (Mul7, ')') => {
-                if state.enabled {
-                    state.sum += state.arg0 * state.arg1;
-                }
+                tokens.push((state.start, Instruction::Mul(state.arg0, state.arg1)));
                 Looking
             }
-            (Looking, 'd') => Do1,
             (Do1, 'o') => Do2,
             (Do2, '(') => Do3,
-            (Do3, ')') => { state.enabled = true; Looking }
+            (Do3, ')') => { tokens.push((state.start, Instruction::Do)); Looking }
             (Do2, 'n') => Dont1,
             (Dont1, '\'') => Dont2,
             (Dont2, 't') => Dont3,
             (Dont3, '(') => Dont4,
-            (Dont4, ')') => { state.enabled = false; Looking } 
+            (Dont4, ')') => { tokens.push((state.start, Instruction::Dont)); Looking }
+            // A character that breaks one instruction may start another
+            (_, 'm') => Mul1,
+            (_, 'd') => Do1,
             _ => Looking
+        };
+        if matches!(state.state, Mul1 | Do1) {
+            state.start = offset;
+        }
+    }
+
+    tokens
+}
+
+//
+// Every instruction starts with an "m" or a "d", so there's no need to
+// try the parser anywhere else.  These find the candidate positions
+// quickly, and only run the parser there.  A candidate inside an
+// instruction that was just found is skipped.
+//
+fn tokenize_candidates(input: &str, candidates: impl Iterator<Item = usize>) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut next = 0;
+    for start in candidates {
+        if start < next {
+            continue;
+        }
+        if let Ok((remaining, instruction)) = instruction(&input[start..]) {
+            tokens.push((start, instruction));
+            next = input.len() - remaining.len();
         }
     }
+    tokens
+}
+
+// Let the memchr crate find the candidates (with SIMD where available)
+pub fn tokenize_memchr(input: &str) -> Vec<Token> {
+    tokenize_candidates(input, memchr::memchr2_iter(b'm', b'd', input.as_bytes()))
+}
+
+pub fn tokenize_swar(input: &str) -> Vec<Token> {
+    tokenize_candidates(input, SwarCandidates { bytes: input.as_bytes(), chunk: 0, mask: 0 })
+}
 
-    state.sum
+pub fn part1_memchr(input: &str) -> u64 {
+    sum_products(tokenize_memchr(input))
+}
+
+pub fn part2_memchr(input: &str) -> u64 {
+    sum_enabled(tokenize_memchr(input))
+}
+
+pub fn part1_swar(input: &str) -> u64 {
+    sum_products(tokenize_swar(input))
+}
+
+pub fn part2_swar(input: &str) -> u64 {
+    sum_enabled(tokenize_swar(input))
+}
+
+//
+// Finds the "m"s and "d"s eight bytes at a time ("SIMD within a
+// register"), using the classic trick for finding a zero byte in a word:
+// XOR with the byte we're looking for repeated in every lane, then
+// (x - 0x01..01) & !x & 0x80..80 sets the high bit of each lane that was
+// zero.  A borrow can also set the bit of the lane above a real match
+// if it XORs to 0x01 (an "l" after an "m", or an "e" after a "d"), so
+// each lane is checked against the byte itself.
+//
+struct SwarCandidates<'a> {
+    bytes: &'a [u8],
+    chunk: usize,   // Offset of the chunk after the one in `mask`
+    mask: u64,      // High bits of the lanes in the current chunk still to report
+}
+
+const LOW_BITS: u64 = 0x0101_0101_0101_0101;
+const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+
+fn zero_lanes(word: u64) -> u64 {
+    word.wrapping_sub(LOW_BITS) & !word & HIGH_BITS
+}
+
+impl Iterator for SwarCandidates<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            while self.mask != 0 {
+                let lane = self.mask.trailing_zeros() as usize / 8;
+                self.mask &= self.mask - 1;
+                let offset = self.chunk - 8 + lane;
+                if matches!(self.bytes[offset], b'm' | b'd') {
+                    return Some(offset);
+                }
+            }
+            if self.chunk >= self.bytes.len() {
+                return None;
+            }
+            // Pad the last chunk with zeros, which never match
+            let mut chunk = [0; 8];
+            let end = self.bytes.len().min(self.chunk + 8);
+            chunk[..end - self.chunk].copy_from_slice(&self.bytes[self.chunk..end]);
+            let word = u64::from_le_bytes(chunk);
+            self.mask = zero_lanes(word ^ (LOW_BITS * b'm' as u64)) | zero_lanes(word ^ (LOW_BITS * b'd' as u64));
+            self.chunk += 8;
+        }
+    }
+}
+
+//
+// How an instruction's numeric arguments may be written: between 1 and
+// `max_digits` decimal digits, optionally preceded by a '-' if `signed`.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgRule {
+    pub max_digits: usize,
+    pub signed: bool,
+}
+
+impl ArgRule {
+    // The rule for the puzzle's mul(): 1-3 digits, no sign
+    pub const PUZZLE: ArgRule = ArgRule { max_digits: 3, signed: false };
+}
+
+//
+// What an instruction does when it's found.  Values from enabled
+// instructions are added to the total.  A value is None if computing it
+// would overflow.
+//
+#[derive(Debug, Clone, Copy)]
+pub enum Semantics {
+    Value(fn(&[i64]) -> Option<i64>),
+    Enable,
+    Disable,
+}
+
+//
+// An instruction is written as its name, then "(", then `arity` arguments
+// separated by ",", then ")".  For example, mul is `name: "mul", arity: 2`.
+//
+#[derive(Debug, Clone, Copy)]
+pub struct InstructionSpec {
+    pub name: &'static str,
+    pub arity: usize,
+    pub arg: ArgRule,
+    pub semantics: Semantics,
+}
+
+impl InstructionSpec {
+    pub const MUL: InstructionSpec = InstructionSpec {
+        name: "mul", arity: 2, arg: ArgRule::PUZZLE, semantics: Semantics::Value(|args| args[0].checked_mul(args[1])),
+    };
+    pub const DO: InstructionSpec = InstructionSpec {
+        name: "do", arity: 0, arg: ArgRule::PUZZLE, semantics: Semantics::Enable,
+    };
+    pub const DONT: InstructionSpec = InstructionSpec {
+        name: "don't", arity: 0, arg: ArgRule::PUZZLE, semantics: Semantics::Disable,
+    };
+}
+
+// The most arguments an instruction can take
+pub const MAX_ARITY: usize = 8;
+
+// Any 18 digit argument fits in an i64.  Values computed from the
+// arguments, and their sum, can still overflow; `run` reports that.
+const MAX_DIGITS: usize = 18;
+
+//
+// Runs a set of user-defined instructions found in corrupted memory.
+// Instead of a hand-written state machine, the recogniser is built from
+// the declarations: the instructions are indexed by the first byte of
+// their names, so each position in the input is only checked against
+// the instructions that could start there.  When more than one matches,
+// the one declared first wins.
+//
+pub struct Interpreter {
+    specs: Vec<InstructionSpec>,
+    by_first_byte: Vec<Vec<usize>>,
+}
+
+impl Interpreter {
+    pub fn new(specs: impl IntoIterator<Item = InstructionSpec>) -> Result<Self, AocError> {
+        let specs: Vec<InstructionSpec> = specs.into_iter().collect();
+        let mut by_first_byte = vec![Vec::new(); 256];
+        for (index, spec) in specs.iter().enumerate() {
+            let Some(&first) = spec.name.as_bytes().first() else {
+                return Err(AocError::new("instruction name is empty"));
+            };
+            if spec.arity > MAX_ARITY {
+                return Err(AocError::new(format!("{}: more than {MAX_ARITY} arguments", spec.name)));
+            }
+            if spec.arity > 0 && !(1..=MAX_DIGITS).contains(&spec.arg.max_digits) {
+                return Err(AocError::new(format!("{}: arguments must allow 1 to {MAX_DIGITS} digits", spec.name)));
+            }
+            by_first_byte[first as usize].push(index);
+        }
+        Ok(Interpreter { specs, by_first_byte })
+    }
+
+    // The instructions for each part of the puzzle
+    pub fn part1() -> Self {
+        Interpreter::new([InstructionSpec::MUL]).unwrap()
+    }
+
+    pub fn part2() -> Self {
+        Interpreter::new([InstructionSpec::MUL, InstructionSpec::DO, InstructionSpec::DONT]).unwrap()
+    }
+
+    //
+    // Find every instruction, and call `found` with its offset, its
+    // declaration, and its arguments.
+    //
+    pub fn scan(&self, input: &str, mut found: impl FnMut(usize, &InstructionSpec, &[i64])) {
+        let bytes = input.as_bytes();
+        let mut args = [0; MAX_ARITY];
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let matched = self.by_first_byte[bytes[offset] as usize].iter().find_map(|&index| {
+                let spec = &self.specs[index];
+                recognise(spec, &bytes[offset..], &mut args).map(|len| (spec, len))
+            });
+            match matched {
+                Some((spec, len)) => {
+                    found(offset, spec, &args[..spec.arity]);
+                    offset += len;
+                }
+                None => offset += 1,
+            }
+        }
+    }
+
+    //
+    // The sum of the values of every enabled instruction.  Instructions
+    // are enabled at the start.  It's an error if a value, or the sum,
+    // overflows an i64.
+    //
+    pub fn run(&self, input: &str) -> Result<i64, AocError> {
+        let mut sum = Some(0i64);
+        let mut overflow = None;
+        let mut enabled = true;
+        self.scan(input, |offset, spec, args| match spec.semantics {
+            Semantics::Value(_) if overflow.is_some() => {}
+            Semantics::Value(value) if enabled => {
+                sum = value(args).and_then(|value| sum?.checked_add(value));
+                if sum.is_none() {
+                    overflow = Some(AocError::new(format!("{} at offset {offset} overflows", spec.name)));
+                }
+            }
+            Semantics::Value(_) => {}
+            Semantics::Enable => enabled = true,
+            Semantics::Disable => enabled = false,
+        });
+        match overflow {
+            Some(err) => Err(err),
+            None => Ok(sum.unwrap()),
+        }
+    }
+}
+
+//
+// If `bytes` starts with a call to the instruction, store its arguments
+// and return the length of the call.
+//
+fn recognise(spec: &InstructionSpec, bytes: &[u8], args: &mut [i64; MAX_ARITY]) -> Option<usize> {
+    let mut pos = spec.name.len();
+    if !bytes.starts_with(spec.name.as_bytes()) || bytes.get(pos) != Some(&b'(') {
+        return None;
+    }
+    pos += 1;
+    for (i, arg) in args.iter_mut().take(spec.arity).enumerate() {
+        if i > 0 {
+            if bytes.get(pos) != Some(&b',') {
+                return None;
+            }
+            pos += 1;
+        }
+        let negative = spec.arg.signed && bytes.get(pos) == Some(&b'-');
+        if negative {
+            pos += 1;
+        }
+        let digits = bytes[pos..].iter().take(spec.arg.max_digits).take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+        let value = bytes[pos..pos + digits].iter().fold(0, |value, &b| value * 10 + (b - b'0') as i64);
+        *arg = if negative { -value } else { value };
+        pos += digits;
+    }
+    (bytes.get(pos) == Some(&b')')).then_some(pos + 1)
+}
+
+pub fn part1_interpreter(input: &str) -> Result<u64, AocError> {
+    let sum = Interpreter::part1().run(input)?;
+    u64::try_from(sum).map_err(|_| AocError::new(format!("sum {sum} is negative")))
+}
+
+pub fn part2_interpreter(input: &str) -> Result<u64, AocError> {
+    let sum = Interpreter::part2().run(input)?;
+    u64::try_from(sum).map_err(|_| AocError::new(format!("sum {sum} is negative")))
 }
 
 #[test]
@@ -156,6 +541,7 @@ fn test_part1() {
     let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
     assert_eq!(part1(input), 161);
     assert_eq!(part1_many_till(input), 161);
+    assert_eq!(part1_state_machine(input), 161);
 }
 
 #[test]
@@ -165,17 +551,126 @@ fn test_part2() {
     assert_eq!(part2_state_machine(input), 48);
 }
 
-#[cfg(test)]
-const FULL_INPUT: &str = include_str!("../input.txt");
+#[test]
+fn test_part2_near_misses() {
+    let input = "mmul(2,3)dmul(1,1)mul(1234,5)mul(5,1234)mul(12,34)don't()domul(9,9)do()mul(1,2)";
+    assert_eq!(part2(input), 6 + 1 + 408 + 2);
+    assert_eq!(part2_state_machine(input), 6 + 1 + 408 + 2);
+    assert_eq!(part2_memchr(input), 6 + 1 + 408 + 2);
+    assert_eq!(part2_swar(input), 6 + 1 + 408 + 2);
+}
+
+#[test]
+fn test_tokenize() {
+    use Instruction::*;
+    let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    let expected = vec![(1, Mul(2, 4)), (20, Dont), (28, Mul(5, 5)), (48, Mul(11, 8)), (59, Do), (64, Mul(8, 5))];
+    assert_eq!(tokenize(input).collect::<Vec<_>>(), expected);
+    assert_eq!(tokenize_state_machine(input), expected);
+    let muls: Vec<Token> = expected.into_iter().filter(|(_, instruction)| matches!(instruction, Mul(..))).collect();
+    assert_eq!(tokenize_many_till(input), muls);
+
+    let input = "mmul(2,3)dmul(1,1)mul(1234,5)mul(12,34)don't()dodo()ümul(1,2)";
+    let expected = vec![(1, Mul(2, 3)), (10, Mul(1, 1)), (29, Mul(12, 34)), (39, Dont), (48, Do), (54, Mul(1, 2))];
+    assert_eq!(tokenize(input).collect::<Vec<_>>(), expected);
+    assert_eq!(tokenize_state_machine(input), expected);
+    assert_eq!(tokenize("").next(), None);
+    assert_eq!(tokenize_many_till("no instructions"), vec![]);
+}
+
+#[test]
+fn test_candidates() {
+    let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    assert_eq!(tokenize_memchr(input), tokenize(input).collect::<Vec<_>>());
+    assert_eq!(tokenize_swar(input), tokenize(input).collect::<Vec<_>>());
+
+    // Every length of a final partial chunk, and an "l" or "e" just
+    // after a match, which the zero-lane trick can report falsely
+    for len in 0..=20 {
+        let input = &"mdxmul(1,2)mldemmdmmlde"[..len];
+        let expected: Vec<usize> = input.bytes().enumerate().filter(|(_, b)| matches!(b, b'm' | b'd')).map(|(i, _)| i).collect();
+        assert_eq!(SwarCandidates { bytes: input.as_bytes(), chunk: 0, mask: 0 }.collect::<Vec<_>>(), expected);
+        assert_eq!(tokenize_swar(input), tokenize(input).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_folds() {
+    use Instruction::*;
+    let tokens = [(0, Mul(2, 3)), (9, Dont), (16, Mul(4, 5)), (25, Do), (29, Mul(1, 7))];
+    assert_eq!(sum_products(tokens), 6 + 20 + 7);
+    assert_eq!(sum_enabled(tokens), 6 + 7);
+}
+
+#[test]
+fn test_interpreter() {
+    let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    assert_eq!(part1_interpreter(input), Ok(161));
+    assert_eq!(part2_interpreter(input), Ok(48));
+    let input = "mmul(2,3)dmul(1,1)mul(1234,5)mul(5,1234)mul(12,34)don't()domul(9,9)do()mul(1,2)";
+    assert_eq!(part2_interpreter(input), Ok(6 + 1 + 408 + 2));
+
+    // Other instructions, arities, and keywords
+    let signed = ArgRule { max_digits: 4, signed: true };
+    let interpreter = Interpreter::new([
+        InstructionSpec { name: "add", arity: 2, arg: signed, semantics: Semantics::Value(|args| args[0].checked_add(args[1])) },
+        InstructionSpec { name: "fma", arity: 3, arg: signed, semantics: Semantics::Value(|args| args[0].checked_mul(args[1])?.checked_add(args[2])) },
+        InstructionSpec { name: "one", arity: 0, arg: signed, semantics: Semantics::Value(|_| Some(1)) },
+        InstructionSpec { name: "on", arity: 0, arg: signed, semantics: Semantics::Enable },
+        InstructionSpec { name: "off", arity: 0, arg: signed, semantics: Semantics::Disable },
+    ]).unwrap();
+    let input = "add(1000,-7)fma(2,3,4)add(12345,1)add(-,1)add(1,+2)off()add(5,5)one()onon()one()fma(2,2)";
+    assert_eq!(interpreter.run(input).unwrap(), 993 + 10 + 1);
+    let mut found = Vec::new();
+    interpreter.scan("xadd(1,-2)one()", |offset, spec, args| found.push((offset, spec.name, args.to_vec())));
+    assert_eq!(found, vec![(1, "add", vec![1, -2]), (10, "one", vec![])]);
+
+    assert!(Interpreter::new([InstructionSpec { name: "", ..InstructionSpec::DO }]).is_err());
+    assert!(Interpreter::new([InstructionSpec { arity: 9, ..InstructionSpec::MUL }]).is_err());
+    assert!(Interpreter::new([InstructionSpec { arg: ArgRule { max_digits: 0, signed: false }, ..InstructionSpec::MUL }]).is_err());
+
+    // The largest arguments fit, but their product or the sum may not
+    let wide = ArgRule { max_digits: 18, signed: true };
+    let interpreter = Interpreter::new([
+        InstructionSpec { arg: wide, ..InstructionSpec::MUL },
+        InstructionSpec { name: "add", arity: 2, arg: wide, semantics: Semantics::Value(|args| args[0].checked_add(args[1])) },
+    ]).unwrap();
+    assert_eq!(interpreter.run("mul(999999999,999999999)").unwrap(), 999999998000000001);
+    assert_eq!(interpreter.run("xmul(999999999999999999,-10)").unwrap_err().to_string(), "mul at offset 1 overflows");
+    let input = "add(999999999999999999,0)".repeat(10);
+    assert_eq!(interpreter.run(&input).unwrap_err().to_string(), "add at offset 225 overflows");
+}
 
 #[test]
 fn test_part1_full_input() {
-    assert_eq!(part1(FULL_INPUT), 181345830);
-    assert_eq!(part1_many_till(FULL_INPUT), 181345830);
+    let input = aoc_utils::full_input!(3);
+    assert_eq!(part1(&input), 181345830);
+    assert_eq!(part1_many_till(&input), 181345830);
 }
 
 #[test]
 fn test_part2_full_input() {
-    assert_eq!(part2(FULL_INPUT), 98729041);
-    assert_eq!(part2_state_machine(FULL_INPUT), 98729041);
+    let input = aoc_utils::full_input!(3);
+    assert_eq!(part2(&input), 98729041);
+    assert_eq!(part2_state_machine(&input), 98729041);
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    aoc_utils::assert_variants_agree::<Day03>(input);
+}
+
+#[test]
+fn test_sums_past_u32() {
+    // Multi-megabyte inputs add up to more than a u32 can hold
+    let input = "mul(999,999)".repeat(5000);
+    assert_eq!(part1(&input), 999 * 999 * 5000);
+    aoc_utils::assert_variants_agree::<Day03>(&input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(3);
+    aoc_utils::assert_variants_agree::<Day03>(&input);
 }
@@ -1,13 +1,12 @@
+use aoc_utils::load_input_or_exit;
 use day03::{part1, part2};
 
 fn main() {
-    let input = include_str!("../input.txt");
+    let input = load_input_or_exit(3);
 
-    let result1 = part1(input);
+    let result1 = part1(&input);
     println!("Part 1: {result1}");
-    assert_eq!(result1, 181345830);
 
-    let result2 = part2(input);
+    let result2 = part2(&input);
     println!("Part 2: {result2}");
-    assert_eq!(result2, 98729041);
 }
@@ -1,22 +1,91 @@
-use day04::{ part1, part2, part2_ndarray };
-
+//
+// The generated grids are bigger than the real 140x140 one, to see how
+// the packed variants (comparing four letters at once, as a u32) fare
+// as the grid grows.
+//
 fn main() {
-    divan::main();
+    aoc_utils::bench::main(4);
 }
 
-const INPUT: &str = include_str!("../input.txt");
+mod full_input {
+    use std::sync::LazyLock;
 
-#[divan::bench]
-fn bench_part1() {
-    part1(INPUT);
-}
+    use aoc_utils::load_input;
+    use day04::{ part1, part1_packed, part1_word_search, part2, part2_ndarray, part2_packed, part2_stencil };
+
+    static INPUT: LazyLock<String> = LazyLock::new(|| load_input(4).expect("input"));
+
+    #[divan::bench]
+    fn bench_part1() {
+        part1(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part1_word_search() {
+        part1_word_search(&INPUT).unwrap();
+    }
+
+    #[divan::bench]
+    fn bench_part1_packed() {
+        part1_packed(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part2() {
+        part2(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part2_ndarray() {
+        part2_ndarray(&INPUT);
+    }
 
-#[divan::bench]
-fn bench_part2() {
-    part2(INPUT);
+    #[divan::bench]
+    fn bench_part2_packed() {
+        part2_packed(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part2_stencil() {
+        part2_stencil(&INPUT).unwrap();
+    }
 }
 
-#[divan::bench]
-fn bench_part2_ndarray() {
-    part2_ndarray(INPUT);
+mod generated {
+    use aoc_utils::bench::generated;
+    use divan::Bencher;
+    use day04::{ part1, part1_packed, part2, part2_ndarray, part2_packed };
+
+    // Side of the square grid (the real one is 140)
+    const SIZES: &[usize] = &[140, 500, 2000];
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1(bencher: Bencher, size: usize) {
+        let input = generated(4, size);
+        bencher.bench(|| part1(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part1_packed(bencher: Bencher, size: usize) {
+        let input = generated(4, size);
+        bencher.bench(|| part1_packed(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2(bencher: Bencher, size: usize) {
+        let input = generated(4, size);
+        bencher.bench(|| part2(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2_ndarray(bencher: Bencher, size: usize) {
+        let input = generated(4, size);
+        bencher.bench(|| part2_ndarray(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_part2_packed(bencher: Bencher, size: usize) {
+        let input = generated(4, size);
+        bencher.bench(|| part2_packed(&input));
+    }
 }
@@ -1,3 +1,48 @@
+use std::str::FromStr;
+use aoc_utils::grid::Pos;
+use aoc_utils::{AocError, Direction8, Grid, Solution, Variant};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    const PART1: &'static [Variant<u32>] = &[
+        Variant::fallible("default", try_part1),
+        Variant::fallible("word_search", part1_word_search),
+        Variant::fallible("packed", |input| { check_grid(input)?; Ok(part1_packed(input)) }),
+    ];
+    const PART2: &'static [Variant<u32>] = &[
+        Variant::fallible("default", try_part2),
+        Variant::fallible("ndarray", |input| { check_grid(input)?; Ok(part2_ndarray(input)) }),
+        Variant::fallible("packed", |input| { check_grid(input)?; Ok(part2_packed(input)) }),
+        Variant::fallible("stencil", part2_stencil),
+    ];
+}
+
+//
+// All of the solutions below assume the input is a square grid.
+//
+fn check_grid(input: &str) -> Result<(), AocError> {
+    let grid = Grid::parse_checked(input, |_| true)?;
+    if grid.rows() != grid.cols() {
+        return Err(AocError::new(format!("expected a square grid, found {} rows and {} columns",
+            grid.rows(), grid.cols())));
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<u32, AocError> {
+    check_grid(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<u32, AocError> {
+    check_grid(input)?;
+    Ok(part2(input))
+}
+
 //
 // Count the number of ways "XMAS" appears in the input.  It could be
 // forwards or backwards, up or down, or diagonal.
@@ -55,8 +100,8 @@ pub fn part1(input: &str) -> u32 {
     // This is not terribly efficient.  It might be better to follow
     // a diagonal from edge to edge, rotating `window` like above.
     // But that's a bit trickier to get right.
-    for col in 0..dimension-3 {
-        for row in 0..(dimension-3) {
+    for col in 0..dimension.saturating_sub(3) {
+        for row in 0..dimension.saturating_sub(3) {
             // We are now looking at 4 characters starting at grid[row][col]
             for i in 0..=3 {
                 window[i] = grid[row+i][col+i];
@@ -70,7 +115,7 @@ pub fn part1(input: &str) -> u32 {
 
     // Look for diagonal words (upper right to lower left)
     for col in 3..dimension {
-        for row in 0..dimension-3 {
+        for row in 0..dimension.saturating_sub(3) {
             // We are now looking at 4 characters starting at grid[row][col]
             for i in 0..=3 {
                 window[i] = grid[row+i][col-i];
@@ -85,6 +130,221 @@ pub fn part1(input: &str) -> u32 {
     result
 }
 
+//
+// A general word search: any words, in a rectangular grid of any
+// characters, read in any of a chosen set of directions.
+//
+pub struct WordSearch {
+    grid: Grid<char>,
+}
+
+//
+// One place a word was found: which of the words it is (an index into
+// the words searched for), where its first letter is, and which way it
+// reads.  A word that reads the same backwards (like "ABA") is found
+// once in each direction.  A one-letter word reads every way, so it's
+// found just once, in the first direction searched.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WordMatch {
+    pub word: usize,
+    pub start: Pos,
+    pub direction: Direction8,
+}
+
+impl FromStr for WordSearch {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, AocError> {
+        Ok(WordSearch { grid: Grid::parse(input, |ch| ch)? })
+    }
+}
+
+impl WordSearch {
+    pub fn new(grid: Grid<char>) -> Self {
+        WordSearch { grid }
+    }
+
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    //
+    // Every match of every word, reading in any of `directions` (e.g.
+    // Direction8::ORTHOGONAL, DIAGONAL or ALL), in order of starting
+    // position, then word, then direction.  If `reversed`, words are also
+    // found backwards; those are reported from the word's first letter,
+    // in the opposite direction.  Empty words are never found.
+    //
+    pub fn find<'a>(&'a self, words: &[&str], directions: &[Direction8], reversed: bool) -> impl Iterator<Item = WordMatch> + 'a {
+        let words: Vec<Vec<char>> = words.iter().map(|word| word.chars().collect()).collect();
+        let mut directions = directions.to_vec();
+        if reversed {
+            directions.extend(directions.clone().into_iter().map(Direction8::reverse));
+        }
+        directions.sort_unstable();
+        directions.dedup();
+
+        let (num_words, num_directions) = (words.len(), directions.len());
+        self.grid.positions()
+            .flat_map(move |start| {
+                (0..num_words).flat_map(move |word| (0..num_directions).map(move |i| (start, word, i)))
+            })
+            .filter_map(move |(start, word, i)| {
+                let letters = &words[word];
+                let found = letters.first() == Some(&self.grid[start]) &&
+                    (letters.len() > 1 || i == 0) &&
+                    self.reads(letters, start, directions[i].delta());
+                found.then_some(WordMatch { word, start, direction: directions[i] })
+            })
+    }
+
+    pub fn count(&self, words: &[&str], directions: &[Direction8], reversed: bool) -> usize {
+        self.find(words, directions, reversed).count()
+    }
+
+    // Whether `word` reads from `start` in the direction `delta`
+    fn reads(&self, word: &[char], start: Pos, delta: (isize, isize)) -> bool {
+        let steps = word.len() as isize - 1;
+        if self.grid.offset(start, (delta.0 * steps, delta.1 * steps)).is_none() {
+            return false;
+        }
+        self.grid.line(start, delta).zip(word).all(|(pos, ch)| self.grid[pos] == *ch)
+    }
+}
+
+//
+// A small 2D template to look for in the grid, like the X of part 2.
+// Cells that are None are wildcards, and match anything.
+//
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stencil {
+    cells: Grid<Option<char>>,
+}
+
+//
+// A place a stencil was found: which of the stencils it is, and where
+// its top left corner is.
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StencilMatch {
+    pub stencil: usize,
+    pub top_left: Pos,
+}
+
+//
+// One row per line, with "." as a wildcard.  For example, a plus sign:
+//   .X.
+//   XXX
+//   .X.
+//
+impl FromStr for Stencil {
+    type Err = AocError;
+
+    fn from_str(input: &str) -> Result<Self, AocError> {
+        let cells = Grid::parse(input, |ch| (ch != '.').then_some(ch))?;
+        if cells.rows() == 0 || cells.cols() == 0 {
+            return Err(AocError::new("empty stencil"));
+        }
+        Ok(Stencil { cells })
+    }
+}
+
+impl Stencil {
+    pub fn new(cells: Grid<Option<char>>) -> Self {
+        Stencil { cells }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.rows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.cols()
+    }
+
+    // A quarter turn clockwise
+    pub fn rotate(&self) -> Stencil {
+        let (rows, cols) = (self.rows(), self.cols());
+        let cells = (0..cols)
+            .flat_map(|row| (0..rows).map(move |col| self.cells[(rows - 1 - col, row)]))
+            .collect();
+        Stencil { cells: Grid::from_vec(cols, rows, cells) }
+    }
+
+    // Mirrored left to right
+    pub fn reflect(&self) -> Stencil {
+        let cols = self.cols();
+        let cells = self.cells.positions().map(|(row, col)| self.cells[(row, cols - 1 - col)]).collect();
+        Stencil { cells: Grid::from_vec(self.rows(), cols, cells) }
+    }
+
+    //
+    // The distinct rotations and reflections of the stencil (up to 8,
+    // fewer if it's symmetrical), starting with the stencil itself.
+    //
+    pub fn orientations(&self) -> Vec<Stencil> {
+        let mut orientations: Vec<Stencil> = Vec::with_capacity(8);
+        let mut stencil = self.clone();
+        for _ in 0..4 {
+            for candidate in [stencil.reflect(), stencil.clone()] {
+                if !orientations.contains(&candidate) {
+                    orientations.push(candidate);
+                }
+            }
+            stencil = stencil.rotate();
+        }
+        // Keep the stencil itself first
+        let index = orientations.iter().position(|s| s == self).unwrap();
+        orientations[..=index].rotate_right(1);
+        orientations
+    }
+
+    fn matches_at(&self, grid: &Grid<char>, (top, left): Pos) -> bool {
+        self.cells.iter().all(|((row, col), cell)| {
+            cell.is_none_or(|ch| grid[(top + row, left + col)] == ch)
+        })
+    }
+}
+
+impl WordSearch {
+    //
+    // Every placement of every stencil that fits entirely inside the
+    // grid, in order of position, then stencil.  To also find rotated or
+    // reflected copies, pass `stencil.orientations()`.
+    //
+    pub fn find_stencils<'a>(&'a self, stencils: &'a [Stencil]) -> impl Iterator<Item = StencilMatch> + 'a {
+        self.grid.positions().flat_map(move |top_left| {
+            stencils.iter()
+                .enumerate()
+                .filter(move |(_, stencil)| {
+                    top_left.0 + stencil.rows() <= self.grid.rows() &&
+                    top_left.1 + stencil.cols() <= self.grid.cols() &&
+                    stencil.matches_at(&self.grid, top_left)
+                })
+                .map(move |(stencil, _)| StencilMatch { stencil, top_left })
+        })
+    }
+
+    pub fn count_stencils(&self, stencils: &[Stencil]) -> usize {
+        self.find_stencils(stencils).count()
+    }
+}
+
+//
+// Part 2 as a stencil: the X of "MAS"es, in each of its orientations.
+//
+pub fn part2_stencil(input: &str) -> Result<u32, AocError> {
+    let search: WordSearch = input.parse()?;
+    let x_mas: Stencil = "M.S\n.A.\nM.S\n".parse()?;
+    Ok(search.count_stencils(&x_mas.orientations()) as u32)
+}
+
+pub fn part1_word_search(input: &str) -> Result<u32, AocError> {
+    let search: WordSearch = input.parse()?;
+    Ok(search.count(&["XMAS"], &Direction8::ALL, false) as u32)
+}
+
 #[test]
 fn test_part1() {
     let input = "\
@@ -100,6 +360,7 @@ MAMMMXMMMM
 MXMXAXMASX
 ";
     assert_eq!(part1(input), 18);
+    assert_eq!(part1_word_search(input), Ok(18));
 }
 
 pub fn part2(input: &str) -> u32 {
@@ -112,8 +373,8 @@ pub fn part2(input: &str) -> u32 {
     let dimension = grid.len();
 
     let mut result = 0;
-    for row in 1..dimension-1 {
-        for col in 1..dimension-1 {
+    for row in 1..dimension.saturating_sub(1) {
+        for col in 1..dimension.saturating_sub(1) {
             if grid[row][col] == b'A' &&
                (grid[row-1][col-1] + grid[row+1][col+1]) == M_AND_S &&
                (grid[row-1][col+1] + grid[row+1][col-1]) == M_AND_S
@@ -141,19 +402,18 @@ MAMMMXMMMM
 MXMXAXMASX
 ";
     assert_eq!(part2(input), 9);
+    assert_eq!(part2_stencil(input), Ok(9));
 }
 
 pub fn part2_ndarray(input: &str) -> u32 {
-    use ndarray::ArrayView;
     const M_AND_S: u8 = b'M' + b'S';
 
-    let dimension = input.find('\n').unwrap();
-    assert_eq!(input.len(), dimension * (dimension + 1));
-    let grid = ArrayView::from_shape((dimension, dimension+1), input.as_bytes()).unwrap();
+    let grid = square_view(input);
+    let (rows, cols) = grid.dim();
 
     let mut result = 0;
-    for row in 1..dimension-1 {
-        for col in 1..dimension-1 {
+    for row in 1..rows.saturating_sub(1) {
+        for col in 1..cols.saturating_sub(1) {
             if grid[[row, col]] == b'A' &&
                (grid[[row-1, col-1]] + grid[[row+1, col+1]]) == M_AND_S &&
                (grid[[row-1, col+1]] + grid[[row+1, col-1]]) == M_AND_S
@@ -182,3 +442,222 @@ MXMXAXMASX
 ";
     assert_eq!(part2_ndarray(input), 9);
 }
+
+#[test]
+fn test_word_search() {
+    use Direction8::*;
+    let search: WordSearch = "\
+CATS
+OXAB
+DOGE
+".parse().unwrap();
+    let matches: Vec<WordMatch> = search.find(&["CAT", "DOG", "TAC", "CXG", "E", ""], &Direction8::ALL, false).collect();
+    assert_eq!(matches, vec![
+        WordMatch { word: 0, start: (0, 0), direction: East },
+        WordMatch { word: 3, start: (0, 0), direction: SouthEast },
+        WordMatch { word: 2, start: (0, 2), direction: West },
+        WordMatch { word: 1, start: (2, 0), direction: East },
+        WordMatch { word: 4, start: (2, 3), direction: North },
+    ]);
+
+    // Only reading forwards, then backwards too
+    assert_eq!(search.count(&["CAT", "TAC", "COD", "DOC"], &[East, South], false), 2);
+    assert_eq!(search.count(&["CAT", "TAC", "COD", "DOC"], &[East, South], true), 4);
+    assert_eq!(search.find(&["TAC"], &[East], true).collect::<Vec<_>>(),
+        vec![WordMatch { word: 0, start: (0, 2), direction: West }]);
+    assert_eq!(search.count(&["CAT", "CXG"], &Direction8::ORTHOGONAL, false), 1);
+    assert_eq!(search.count(&["CAT", "CXG"], &Direction8::DIAGONAL, false), 1);
+    // Longer than the grid
+    assert_eq!(search.count(&["CATSX"], &Direction8::ALL, false), 0);
+    // Palindromes are found both ways
+    assert_eq!("ABA\n".parse::<WordSearch>().unwrap().count(&["ABA"], &Direction8::ALL, false), 2);
+    assert_eq!("ABA\n".parse::<WordSearch>().unwrap().count(&["ABA"], &[East], true), 2);
+    assert!("AB\nA\n".parse::<WordSearch>().is_err());
+}
+
+//
+// The input as an ndarray view of the letters (square, once check_grid
+// has passed), stepping over the line endings.  The last line doesn't
+// need a newline.
+//
+fn square_view(input: &str) -> ndarray::ArrayView2<'_, u8> {
+    use ndarray::{ArrayView, ShapeBuilder};
+
+    let rows = input.lines().count();
+    let cols = input.lines().next().map_or(0, str::len);
+    let stride = input.find('\n').map_or(input.len(), |end| end + 1);
+    ArrayView::from_shape((rows, cols).strides((stride, 1)), input.as_bytes()).expect("rectangular grid")
+}
+
+//
+// The grid, shifted so that element [r, c] of the result is the letter
+// `step` steps from [r, c] in the direction `delta`, for every [r, c]
+// where a `len` letter word starting there in that direction would fit.
+// Stacking up the views for steps 0, 1, 2, ... lines up the letters of
+// every such word.
+//
+fn shifted(grid: ndarray::ArrayView2<'_, u8>, step: usize, delta: (isize, isize), len: usize) -> ndarray::ArrayView2<'_, u8> {
+    use ndarray::s;
+
+    let span = len - 1;
+    let range = |n: usize, d: isize| match d {
+        0 => 0..n,
+        1 => step..n - span + step,
+        _ => span - step..n - step,
+    };
+    let (rows, cols) = grid.dim();
+    grid.slice_move(s![range(rows, delta.0), range(cols, delta.1)])
+}
+
+//
+// Count the places where four lined up views spell one of `patterns`,
+// with each group of four letters packed into a u32 (first letter in
+// the high byte) so that it's a single comparison.
+//
+fn count_packed(views: [ndarray::ArrayView2<'_, u8>; 4], patterns: &[u32]) -> u32 {
+    let [a, b, c, d] = views;
+    ndarray::Zip::from(&a).and(&b).and(&c).and(&d)
+        .fold(0, |count, &a, &b, &c, &d| {
+            let packed = (a as u32) << 24 | (b as u32) << 16 | (c as u32) << 8 | d as u32;
+            count + patterns.contains(&packed) as u32
+        })
+}
+
+//
+// Part 1, comparing four letters at a time.  For each of the four
+// directions (a word read backwards is the same as "SAMX" forwards),
+// shifted copies of the grid line up the letters of every possible word.
+//
+pub fn part1_packed(input: &str) -> u32 {
+    let grid = square_view(input);
+    if grid.nrows() < 4 {
+        return 0;
+    }
+    let patterns = [u32::from_be_bytes(*b"XMAS"), u32::from_be_bytes(*b"SAMX")];
+    [(0, 1), (1, 0), (1, 1), (1, -1)].into_iter()
+        .map(|delta| {
+            let views = [0, 1, 2, 3].map(|step| shifted(grid, step, delta, 4));
+            count_packed(views, &patterns)
+        })
+        .sum()
+}
+
+//
+// Part 2, packing the four corners of each X into a u32 (top left, top
+// right, bottom left, bottom right), and comparing against the four ways
+// the two "MAS"es can be written.  The views of the corners all have the
+// same shape as the view of the centers.
+//
+pub fn part2_packed(input: &str) -> u32 {
+    use ndarray::s;
+
+    let grid = square_view(input);
+    let n = grid.nrows();
+    if n < 3 {
+        return 0;
+    }
+    let patterns = [*b"MMSS", *b"MSMS", *b"SMSM", *b"SSMM"].map(u32::from_be_bytes);
+    let centers = grid.slice(s![1..n-1, 1..n-1]);
+    let [top_left, top_right, bottom_left, bottom_right] = [(0, 0), (0, 2), (2, 0), (2, 2)]
+        .map(|(row, col)| grid.slice_move(s![row..row+n-2, col..col+n-2]));
+    ndarray::Zip::from(&centers).and(&top_left).and(&top_right).and(&bottom_left).and(&bottom_right)
+        .fold(0, |count, &center, &tl, &tr, &bl, &br| {
+            let corners = (tl as u32) << 24 | (tr as u32) << 16 | (bl as u32) << 8 | br as u32;
+            count + (center == b'A' && patterns.contains(&corners)) as u32
+        })
+}
+
+#[test]
+fn test_packed() {
+    let input = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+    assert_eq!(part1_packed(input), 18);
+    assert_eq!(part2_packed(input), 9);
+    // A row, a column, and a diagonal from the same X
+    assert_eq!(part1_packed("XMAS\nMMAA\nAAAM\nSAMS\n"), 3);
+    assert_eq!(part1("XMAS\nMMAA\nAAAM\nSAMS\n"), 3);
+    assert_eq!(part1_packed("XMA\nMMA\nAAM\n"), 0);
+    assert_eq!(part2_packed("MXS\nXAX\nMXS\n"), 1);
+    assert_eq!(part2_packed("MXM\nXAX\nSXM\n"), 0);
+    assert_eq!(part2_packed("MS\nMS\n"), 0);
+    // No newline at the end of the last line
+    assert_eq!(part1_packed("XMAS\nMMAA\nAAAM\nSAMS"), 3);
+    assert_eq!(part2_packed("MXS\nXAX\nMXS"), 1);
+    assert_eq!(part2_ndarray("MXS\nXAX\nMXS"), 1);
+}
+
+#[test]
+fn test_stencil() {
+    let stencil: Stencil = "AB\n.C\n".parse().unwrap();
+    assert_eq!(stencil.rotate(), ".A\nCB\n".parse().unwrap());
+    assert_eq!(stencil.reflect(), "BA\nC.\n".parse().unwrap());
+    assert_eq!(stencil.rotate().rotate().rotate().rotate(), stencil);
+    assert_eq!(stencil.orientations().len(), 8);
+    assert_eq!(stencil.orientations()[0], stencil);
+
+    // Symmetrical stencils have fewer distinct orientations
+    let plus: Stencil = ".X.\nXXX\n.X.\n".parse().unwrap();
+    assert_eq!(plus.orientations(), vec![plus.clone()]);
+    let x_mas: Stencil = "M.S\n.A.\nM.S\n".parse().unwrap();
+    assert_eq!(x_mas.orientations().len(), 4);
+    let line: Stencil = "MAS\n".parse().unwrap();
+    assert_eq!(line.orientations().len(), 4);
+    assert_eq!(line.rotate().rows(), 3);
+
+    let search: WordSearch = "\
+.X..
+XXX.
+.X..
+".parse().unwrap();
+    assert_eq!(search.find_stencils(std::slice::from_ref(&plus)).collect::<Vec<_>>(),
+        vec![StencilMatch { stencil: 0, top_left: (0, 0) }]);
+    // "MAS" across and down; the other orientations are "SAM" and "MAS" up
+    let search: WordSearch = "MAS.\nA...\nS...\n".parse().unwrap();
+    assert_eq!(search.find_stencils(&line.orientations()).collect::<Vec<_>>(), vec![
+        StencilMatch { stencil: 0, top_left: (0, 0) },
+        StencilMatch { stencil: 2, top_left: (0, 0) },
+    ]);
+    assert!("".parse::<Stencil>().is_err());
+    assert!("AB\nC\n".parse::<Stencil>().is_err());
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("XMAS\nXMAS\n").unwrap_err().to_string(), "expected a square grid, found 2 rows and 4 columns");
+    assert_eq!(try_part2("XM\nX\n").unwrap_err().to_string(), "line 2: expected 2 columns, found 1");
+    assert_eq!(try_part1("XM\nAS\n"), Ok(0));
+    assert_eq!(try_part2("X\n"), Ok(0));
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+    aoc_utils::assert_variants_agree::<Day04>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(4);
+    aoc_utils::assert_variants_agree::<Day04>(&input);
+}
@@ -1,37 +1,113 @@
-use day05::{ part1, part2, both_parts, both_parts_faster, both_parts_hashset, both_parts_nom };
-
+//
+// The both_parts variants differ in how they look up rules and sort
+// updates, so the generated inputs have many more updates than the
+// real one.
+//
 fn main() {
-    divan::main();
+    aoc_utils::bench::main(5);
 }
 
-const INPUT: &str = include_str!("../input.txt");
+mod full_input {
+    use std::sync::LazyLock;
 
-#[divan::bench]
-fn bench_part1() {
-    part1(INPUT);
-}
+    use aoc_utils::load_input;
+    use day05::{ part1, part2, both_parts, both_parts_faster, both_parts_hashset, both_parts_nom, both_parts_middle, both_parts_graph, both_parts_stream };
 
-#[divan::bench]
-fn bench_part2() {
-    part2(INPUT);
-}
+    static INPUT: LazyLock<String> = LazyLock::new(|| load_input(5).expect("input"));
 
-#[divan::bench]
-fn bench_both_parts() {
-    both_parts(INPUT);
-}
+    #[divan::bench]
+    fn bench_part1() {
+        part1(&INPUT);
+    }
 
-#[divan::bench]
-fn bench_both_parts_faster() {
-    both_parts_faster(INPUT);
-}
+    #[divan::bench]
+    fn bench_part2() {
+        part2(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts() {
+        both_parts(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts_faster() {
+        both_parts_faster(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts_hashset() {
+        both_parts_hashset(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts_nom() {
+        both_parts_nom(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts_middle() {
+        both_parts_middle(&INPUT).unwrap();
+    }
 
-#[divan::bench]
-fn bench_both_parts_hashset() {
-    both_parts_hashset(INPUT);
+    #[divan::bench]
+    fn bench_both_parts_graph() {
+        both_parts_graph(&INPUT).unwrap();
+    }
+
+    #[divan::bench]
+    fn bench_both_parts_stream() {
+        both_parts_stream(INPUT.as_bytes()).unwrap();
+    }
 }
 
-#[divan::bench]
-fn bench_both_parts_nom() {
-    both_parts_nom(INPUT);
+mod generated {
+    use aoc_utils::bench::generated;
+    use divan::Bencher;
+    use day05::{ both_parts, both_parts_faster, both_parts_hashset, both_parts_nom, both_parts_middle, both_parts_graph, both_parts_stream };
+
+    // Updates (the real input has about 200)
+    const SIZES: &[usize] = &[200, 2000, 20000];
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts(bencher: Bencher, size: usize) {
+        let input = generated(5, size);
+        bencher.bench(|| both_parts(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_faster(bencher: Bencher, size: usize) {
+        let input = generated(5, size);
+        bencher.bench(|| both_parts_faster(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_hashset(bencher: Bencher, size: usize) {
+        let input = generated(5, size);
+        bencher.bench(|| both_parts_hashset(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_nom(bencher: Bencher, size: usize) {
+        let input = generated(5, size);
+        bencher.bench(|| both_parts_nom(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_middle(bencher: Bencher, size: usize) {
+        let input = generated(5, size);
+        bencher.bench(|| both_parts_middle(&input).unwrap());
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_graph(bencher: Bencher, size: usize) {
+        let input = generated(5, size);
+        bencher.bench(|| both_parts_graph(&input).unwrap());
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_stream(bencher: Bencher, size: usize) {
+        let input = generated(5, size);
+        bencher.bench(|| both_parts_stream(input.as_bytes()).unwrap());
+    }
 }
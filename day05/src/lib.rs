@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, collections::{HashMap, HashSet}};
+use std::{cmp::Ordering, collections::{HashMap, HashSet, VecDeque}, io::BufRead, str::FromStr};
 
 use itertools::Itertools;
 use nom::{
@@ -9,6 +9,277 @@ use nom::{
     sequence::{pair, separated_pair, terminated},
     IResult
 };
+use aoc_utils::{AocError, LineContext, Solution, Variant};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    const PART1: &'static [Variant<u32>] = &[
+        Variant::fallible("both_parts", |input| check_input(input).map(|_| both_parts(input).0)),
+        Variant::fallible("default", try_part1),
+        Variant::fallible("faster", |input| check_input(input).map(|_| both_parts_faster(input).0)),
+        Variant::fallible("hashset", |input| check_input(input).map(|_| both_parts_hashset(input).0)),
+        Variant::fallible("nom", |input| check_input(input).map(|_| both_parts_nom(input).0)),
+        Variant::fallible("middle", |input| both_parts_middle(input).map(|(part1, _)| part1)),
+        Variant::fallible("stream", |input| both_parts_stream(input.as_bytes()).map(|(part1, _)| part1)),
+        Variant::fallible("graph", |input| both_parts_graph(input).map(|(part1, _)| part1)),
+    ];
+    const PART2: &'static [Variant<u32>] = &[
+        Variant::fallible("both_parts", |input| check_input(input).map(|_| both_parts(input).1)),
+        Variant::fallible("default", try_part2),
+        Variant::fallible("faster", |input| check_input(input).map(|_| both_parts_faster(input).1)),
+        Variant::fallible("hashset", |input| check_input(input).map(|_| both_parts_hashset(input).1)),
+        Variant::fallible("nom", |input| check_input(input).map(|_| both_parts_nom(input).1)),
+        Variant::fallible("middle", |input| both_parts_middle(input).map(|(_, part2)| part2)),
+        Variant::fallible("stream", |input| both_parts_stream(input.as_bytes()).map(|(_, part2)| part2)),
+        Variant::fallible("graph", |input| both_parts_graph(input).map(|(_, part2)| part2)),
+    ];
+}
+
+//
+// All of the solutions below assume that there is a rule for every pair
+// of pages that appear together in an update.  Check that, and the
+// format of the input, before trusting them with it.
+//
+fn check_input(input: &str) -> Result<(), AocError> {
+    let (rules, updates) = input.split_once("\n\n")
+        .ok_or_else(|| AocError::new("expected a blank line between the rules and the updates"))?;
+    let rule_lines = rules.lines().count() + 1;
+
+    let order: PageOrder = rules.parse()?;
+
+    for (i, line) in updates.lines().enumerate() {
+        parse_checked_update(&order, rule_lines + i + 1, line)?;
+    }
+
+    Ok(())
+}
+
+//
+// The pages of the update on line `line`, checking that no page appears
+// twice and that there's a rule for every pair.
+//
+fn parse_checked_update(order: &PageOrder, line: usize, text: &str) -> Result<Vec<u32>, AocError> {
+    let pages: Vec<u32> = text.split(',').map(str::parse).try_collect().on_line(line)?;
+    for (left, right) in pages.iter().tuple_combinations() {
+        if left == right {
+            return Err(AocError::at_line(line, format!("page {left} appears more than once")));
+        }
+        if order.compare(left, right) == Ordering::Equal {
+            return Err(AocError::at_line(line, format!("no rule orders pages {left} and {right}")));
+        }
+    }
+    Ok(pages)
+}
+
+pub fn try_part1(input: &str) -> Result<u32, AocError> {
+    check_input(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<u32, AocError> {
+    check_input(input)?;
+    Ok(part2(input))
+}
+
+//
+// The rules, one per line, like "47|53".
+//
+fn parse_rules_text(rules: &str) -> Result<Vec<(u32, u32)>, AocError> {
+    rules.lines().enumerate().map(|(i, text)| parse_rule_line(i + 1, text)).collect()
+}
+
+//
+// One rule, like "47|53", on line `line`.
+//
+fn parse_rule_line(line: usize, text: &str) -> Result<(u32, u32), AocError> {
+    let (left, right) = text.split_once('|')
+        .ok_or_else(|| AocError::at_line(line, format!("expected a rule like 47|53, found {text:?}")))?;
+    Ok((left.parse().on_line(line)?, right.parse().on_line(line)?))
+}
+
+//
+// The order the rules put pages in, for comparing and sorting the pages
+// of an update.  Pages with no rule between them compare as equal; the
+// puzzle promises that doesn't happen within an update (see
+// `PrecedenceGraph` for input that doesn't keep that promise).
+//
+#[derive(Debug, Clone, Default)]
+pub struct PageOrder {
+    rules: HashSet<(u32, u32)>,
+}
+
+impl FromStr for PageOrder {
+    type Err = AocError;
+
+    fn from_str(rules: &str) -> Result<Self, AocError> {
+        Ok(parse_rules_text(rules)?.into_iter().collect())
+    }
+}
+
+impl FromIterator<(u32, u32)> for PageOrder {
+    fn from_iter<I: IntoIterator<Item = (u32, u32)>>(rules: I) -> Self {
+        PageOrder { rules: rules.into_iter().collect() }
+    }
+}
+
+impl PageOrder {
+    // Whether a rule says `left` comes before `right`.  Usable with
+    // `is_sorted_by`.
+    pub fn is_before(&self, left: &u32, right: &u32) -> bool {
+        self.rules.contains(&(*left, *right))
+    }
+
+    // A comparator for `sort_by` and friends
+    pub fn compare(&self, left: &u32, right: &u32) -> Ordering {
+        if self.is_before(left, right) {
+            Ordering::Less
+        } else if self.is_before(right, left) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    pub fn is_sorted(&self, pages: &[u32]) -> bool {
+        pages.is_sorted_by(|left, right| self.is_before(left, right))
+    }
+
+    pub fn sort(&self, pages: &mut [u32]) {
+        pages.sort_by(|left, right| self.compare(left, right));
+    }
+
+    //
+    // The page that would be in the middle if the update were sorted,
+    // without sorting it.  This is quickselect: split the pages into
+    // those before and after one of them, and keep only the side the
+    // middle is on, which takes linear time on average.  None if there
+    // are no pages, or if two pages it compares have no rule between them.
+    //
+    pub fn middle_page(&self, pages: &[u32]) -> Option<u32> {
+        let mut pages = pages.to_vec();
+        let mut middle = pages.len() / 2;
+        loop {
+            let pivot = pages.len() / 2;
+            let &page = pages.get(pivot)?;
+            let mut before = Vec::new();
+            let mut after = Vec::new();
+            for (i, other) in pages.iter().enumerate() {
+                match self.compare(other, &page) {
+                    _ if i == pivot => {}
+                    Ordering::Less => before.push(*other),
+                    Ordering::Greater => after.push(*other),
+                    Ordering::Equal => return None,
+                }
+            }
+            match middle.cmp(&before.len()) {
+                Ordering::Less => pages = before,
+                Ordering::Equal => return Some(page),
+                Ordering::Greater => {
+                    middle -= before.len() + 1;
+                    pages = after;
+                }
+            }
+        }
+    }
+}
+
+//
+// One update, checked against the rules.  `pages` are in the order the
+// rules require, and `valid` says whether the update already was.
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedUpdate {
+    pub line: usize,
+    pub valid: bool,
+    pub pages: Vec<u32>,
+    pub middle: u32,
+}
+
+//
+// Reads the rules, then checks the updates one at a time as they're
+// read, so only one update is in memory at once.
+//
+pub struct UpdateStream<R> {
+    reader: R,
+    order: PageOrder,
+    line: usize,
+    buffer: String,
+}
+
+//
+// Read the rules, up to the blank line, from `reader`.  The updates are
+// read as the returned stream is iterated.
+//
+pub fn read_updates<R: BufRead>(mut reader: R) -> Result<UpdateStream<R>, AocError> {
+    let mut rules = Vec::new();
+    let mut buffer = String::new();
+    let mut line = 0;
+    loop {
+        buffer.clear();
+        line += 1;
+        let bytes = reader.read_line(&mut buffer).map_err(|err| AocError::at_line(line, err.to_string()))?;
+        let text = buffer.trim_end_matches(['\n', '\r']);
+        if bytes == 0 {
+            return Err(AocError::new("expected a blank line between the rules and the updates"));
+        }
+        if text.is_empty() {
+            break;
+        }
+        rules.push(parse_rule_line(line, text)?);
+    }
+    Ok(UpdateStream { reader, order: rules.into_iter().collect(), line, buffer })
+}
+
+impl<R> UpdateStream<R> {
+    pub fn order(&self) -> &PageOrder {
+        &self.order
+    }
+
+    fn check(&self, text: &str) -> Result<CheckedUpdate, AocError> {
+        let mut pages = parse_checked_update(&self.order, self.line, text)?;
+        let valid = self.order.is_sorted(&pages);
+        if !valid {
+            self.order.sort(&mut pages);
+        }
+        let middle = pages[pages.len() / 2];
+        Ok(CheckedUpdate { line: self.line, valid, pages, middle })
+    }
+}
+
+impl<R: BufRead> Iterator for UpdateStream<R> {
+    type Item = Result<CheckedUpdate, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        self.line += 1;
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => None,
+            Ok(_) => Some(self.check(self.buffer.trim_end_matches(['\n', '\r']))),
+            Err(err) => Some(Err(AocError::at_line(self.line, err.to_string()))),
+        }
+    }
+}
+
+//
+// Both parts, from a stream of updates.
+//
+pub fn both_parts_stream(reader: impl BufRead) -> Result<(u32, u32), AocError> {
+    let mut part1 = 0;
+    let mut part2 = 0;
+    for update in read_updates(reader)? {
+        let update = update?;
+        if update.valid {
+            part1 += update.middle;
+        } else {
+            part2 += update.middle;
+        }
+    }
+    Ok((part1, part2))
+}
 
 //
 // The task is to check whether the various lists of numbers are sorted
@@ -33,21 +304,13 @@ pub fn part1(input: &str) -> u32 {
     let (rules, updates) = input.split_once("\n\n").unwrap();
 
     // Parse the ordering rules
-    let mut is_sorted = HashMap::<(u32, u32), bool>::new();
-    for line in rules.lines() {
-        if line.is_empty() { break; }
-        let (left, right) = line.split_once('|').unwrap();
-        let left: u32 = left.parse().unwrap();
-        let right: u32 = right.parse().unwrap();
-        is_sorted.insert((left, right), true);
-        is_sorted.insert((right, left), false);
-    }
+    let order: PageOrder = rules.parse().unwrap();
 
     // Parse the lists of numbers
     'update: for line in updates.lines() {
         let pages = line.split(',').map(|word| word.parse::<u32>().unwrap()).collect_vec();
         for (left, right) in pages.iter().tuple_windows() {
-            if !(is_sorted.get(&(*left, *right)).unwrap()) { continue 'update; }
+            if !order.is_before(left, right) { continue 'update; }
         }
         result += pages[pages.len()/2]
     }
@@ -92,52 +355,20 @@ fn test_part1() {
     assert_eq!(part1(input), 143);
 }
 
-//
-// Sort the list of page numbers based on the given sorting rules.
-// Returns true if the list was modified.
-//
-fn sort_pages(pages: &mut[u32], is_sorted: &HashMap<(u32, u32), bool>) -> bool {
-    let mut result = false;
-    let mut changed = true;
-
-    // A simple, poorly optimized, bubble sort
-    while changed {
-        changed = false;
-        for i in 1..pages.len() {
-            let left = pages[i-1];
-            let right = pages[i];
-            if !is_sorted.get(&(left, right)).unwrap() {
-                changed = true;
-                result = true;
-                pages.swap(i-1, i);
-            }
-        }
-    }
-
-    result
-}
-
 pub fn part2(input: &str) -> u32 {
     let mut result = 0;
     let (rules, updates) = input.split_once("\n\n").unwrap();
 
     // Parse the ordering rules
-    let mut is_sorted = HashMap::<(u32, u32), bool>::new();
-    for line in rules.lines() {
-        if line.is_empty() { break; }
-        let (left, right) = line.split_once('|').unwrap();
-        let left: u32 = left.parse().unwrap();
-        let right: u32 = right.parse().unwrap();
-        is_sorted.insert((left, right), true);
-        is_sorted.insert((right, left), false);
-    }
+    let order: PageOrder = rules.parse().unwrap();
 
     // Parse the lists of numbers
     for line in updates.lines() {
         // dbg!(line);
         let mut pages = line.split(',').map(|word| word.parse::<u32>().unwrap()).collect_vec();
-        if sort_pages(&mut pages, &is_sorted) {
+        if !order.is_sorted(&pages) {
             // They were not in sorted order, so add the new middle element to result
+            order.sort(&mut pages);
             // dbg!(&pages);
             result += pages[pages.len()/2]
         }
@@ -187,22 +418,15 @@ pub fn both_parts(input: &str) -> (u32, u32) {
     let (rules, updates) = input.split_once("\n\n").unwrap();
 
     // Parse the ordering rules
-    let mut is_sorted = HashMap::<(u32, u32), bool>::new();
-    for line in rules.lines() {
-        if line.is_empty() { break; }
-        let (left, right) = line.split_once('|').unwrap();
-        let left: u32 = left.parse().unwrap();
-        let right: u32 = right.parse().unwrap();
-        is_sorted.insert((left, right), true);
-        is_sorted.insert((right, left), false);
-    }
+    let order: PageOrder = rules.parse().unwrap();
 
     // Parse the lists of numbers
     for line in updates.lines() {
         // dbg!(line);
         let mut pages = line.split(',').map(|word| word.parse::<u32>().unwrap()).collect_vec();
-        if sort_pages(&mut pages, &is_sorted) {
+        if !order.is_sorted(&pages) {
             // They were not in sorted order, so add the new middle element to result
+            order.sort(&mut pages);
             // dbg!(&pages);
             part2 += pages[pages.len()/2]
         } else {
@@ -256,36 +480,18 @@ pub fn both_parts_faster(input: &str) -> (u32, u32) {
     let (rules, updates) = input.split_once("\n\n").unwrap();
 
     // Parse the ordering rules
-    let mut is_sorted = HashMap::<(u32, u32), bool>::new();
-    for line in rules.lines() {
-        if line.is_empty() { break; }
-        let (left, right) = line.split_once('|').unwrap();
-        let left: u32 = left.parse().unwrap();
-        let right: u32 = right.parse().unwrap();
-        is_sorted.insert((left, right), true);
-        is_sorted.insert((right, left), false);
-    }
-
-    let compare_pages = |left: &u32, right: &u32| -> Ordering{
-        if left == right {
-            Ordering::Equal
-        } else if *is_sorted.get(&(*left, *right)).unwrap() {
-                Ordering::Less
-        } else {
-            Ordering::Greater
-        }
-    };
+    let order: PageOrder = rules.parse().unwrap();
 
     // Parse the lists of numbers
     for line in updates.lines() {
         // dbg!(line);
         let mut pages = line.split(',').map(|word| word.parse::<u32>().unwrap()).collect_vec();
-        if pages.is_sorted_by(|left, right| *is_sorted.get(&(*left, *right)).unwrap()) {
+        if pages.is_sorted_by(|left, right| order.is_before(left, right)) {
             // They were not in sorted order, so add the new middle element to result
             // dbg!(&pages);
             part1 += pages[pages.len()/2]
         } else {
-            pages.sort_by(compare_pages);
+            pages.sort_by(|left, right| order.compare(left, right));
             part2 += pages[pages.len()/2]
         }
     }
@@ -329,42 +535,29 @@ fn test_both_parts_faster() {
     assert_eq!(both_parts_faster(input), (143, 123));
 }
 
+//
+// Like `both_parts_faster`, but an unstable sort is enough, since no two
+// pages in an update are equal.
+//
 pub fn both_parts_hashset(input: &str) -> (u32, u32) {
     let mut part1 = 0;
     let mut part2 = 0;
     let (rules, updates) = input.split_once("\n\n").unwrap();
 
     // Parse the ordering rules
-    let mut is_sorted = HashSet::<(u32, u32)>::new();
-    for line in rules.lines() {
-        if line.is_empty() { break; }
-        let (left, right) = line.split_once('|').unwrap();
-        let left: u32 = left.parse().unwrap();
-        let right: u32 = right.parse().unwrap();
-        is_sorted.insert((left, right));
-    }
-
-    let compare_pages = |left: &u32, right: &u32| -> Ordering{
-        if left == right {
-            Ordering::Equal
-        } else if is_sorted.contains(&(*left, *right)) {
-                Ordering::Less
-        } else {
-            Ordering::Greater
-        }
-    };
+    let order: PageOrder = rules.parse().unwrap();
 
     // Parse the lists of numbers
     for line in updates.lines() {
         // dbg!(line);
         let mut pages = line.split(',').map(|word| word.parse::<u32>().unwrap()).collect_vec();
-        if pages.is_sorted_by(|left, right| is_sorted.contains(&(*left, *right))) {
+        if order.is_sorted(&pages) {
             // They were in sorted order, so add the new middle element to part1
             // dbg!(&pages);
             part1 += pages[pages.len()/2]
         } else {
             // They were not sorted, so sort and add to part2
-            pages.sort_by(compare_pages);
+            pages.sort_unstable_by(|left, right| order.compare(left, right));
             part2 += pages[pages.len()/2]
         }
     }
@@ -372,6 +565,33 @@ pub fn both_parts_hashset(input: &str) -> (u32, u32) {
     (part1, part2)
 }
 
+//
+// Part 2 only needs the middle page of each update, not the whole
+// sorted update, so find it without sorting.  An update with pages that
+// have no rule between them is an error.
+//
+pub fn both_parts_middle(input: &str) -> Result<(u32, u32), AocError> {
+    let mut part1 = 0;
+    let mut part2 = 0;
+    let (rules, updates) = input.split_once("\n\n")
+        .ok_or_else(|| AocError::new("expected a blank line between the rules and the updates"))?;
+    let order: PageOrder = rules.parse()?;
+    let rule_lines = rules.lines().count() + 1;
+
+    for (i, line) in updates.lines().enumerate() {
+        let line_number = rule_lines + i + 1;
+        let pages: Vec<u32> = line.split(',').map(str::parse).try_collect().on_line(line_number)?;
+        if order.is_sorted(&pages) {
+            part1 += pages[pages.len()/2]
+        } else {
+            part2 += order.middle_page(&pages)
+                .ok_or_else(|| AocError::at_line(line_number, "some pages have no rule between them"))?
+        }
+    }
+
+    Ok((part1, part2))
+}
+
 #[test]
 fn test_both_parts_hashset() {
     let input = "\
@@ -439,26 +659,17 @@ fn parse_input(input: &str) -> IResult<&str, (Rules, PageLists)> {
 pub fn both_parts_nom(input: &str) -> (u32, u32) {
     let mut part1 = 0;
     let mut part2 = 0;
-    let (_, (is_sorted, page_lists)) = parse_input(input).unwrap();
-
-    let compare_pages = |left: &u32, right: &u32| -> Ordering{
-        if left == right {
-            Ordering::Equal
-        } else if is_sorted.contains(&(*left, *right)) {
-                Ordering::Less
-        } else {
-            Ordering::Greater
-        }
-    };
+    let (_, (rules, page_lists)) = parse_input(input).unwrap();
+    let order = PageOrder::from_iter(rules);
 
     for mut pages in page_lists {
-        if pages.is_sorted_by(|left, right| is_sorted.contains(&(*left, *right))) {
+        if order.is_sorted(&pages) {
             // They were in sorted order, so add the new middle element to part1
             // dbg!(&pages);
             part1 += pages[pages.len()/2]
         } else {
             // They were not sorted, so sort and add to part2
-            pages.sort_by(compare_pages);
+            order.sort(&mut pages);
             part2 += pages[pages.len()/2]
         }
     }
@@ -500,3 +711,451 @@ fn test_both_parts_nom() {
 ";
     assert_eq!(both_parts_nom(input), (143, 123));
 }
+
+//
+// The rules as a directed graph, with an edge from X to Y for each rule
+// X|Y.  This doesn't assume anything about the rules: they may have
+// cycles (the full input does, across all of its pages), and updates may
+// contain pairs of pages with no rule between them.
+//
+#[derive(Debug, Clone, Default)]
+pub struct PrecedenceGraph {
+    rules: HashSet<(u32, u32)>,
+    after: HashMap<u32, Vec<u32>>,
+}
+
+//
+// How the rules order the pages of one update.
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOrder {
+    // There's a rule for every pair of pages, and they agree.  These are
+    // the pages in that order.
+    Total(Vec<u32>),
+    // Some pairs had no rule, so those were ordered by following rules
+    // through other pages (the transitive closure).  Pairs that still
+    // aren't ordered keep their order from the update.
+    Inferred(Vec<u32>),
+    // The rules contradict each other.  These pages form a cycle, with
+    // each page required to come before the next, and the last before
+    // the first.
+    Cyclic(Vec<u32>),
+}
+
+impl UpdateOrder {
+    // The pages in order, if there is one
+    pub fn pages(&self) -> Option<&[u32]> {
+        match self {
+            UpdateOrder::Total(pages) | UpdateOrder::Inferred(pages) => Some(pages),
+            UpdateOrder::Cyclic(_) => None,
+        }
+    }
+}
+
+impl FromStr for PrecedenceGraph {
+    type Err = AocError;
+
+    fn from_str(rules: &str) -> Result<Self, AocError> {
+        Ok(PrecedenceGraph::from_rules(parse_rules_text(rules)?))
+    }
+}
+
+impl PrecedenceGraph {
+    pub fn from_rules(rules: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        let mut graph = PrecedenceGraph::default();
+        for (left, right) in rules {
+            if graph.rules.insert((left, right)) {
+                graph.after.entry(left).or_default().push(right);
+            }
+        }
+        graph
+    }
+
+    // Every page mentioned in a rule, in increasing order
+    pub fn pages(&self) -> Vec<u32> {
+        let pages: HashSet<u32> = self.rules.iter().flat_map(|&(left, right)| [left, right]).collect();
+        pages.into_iter().sorted_unstable().collect()
+    }
+
+    // Whether there is a rule saying `left` comes before `right`
+    pub fn has_rule(&self, left: u32, right: u32) -> bool {
+        self.rules.contains(&(left, right))
+    }
+
+    // Whether a chain of one or more rules leads from `left` to `right`
+    pub fn reaches(&self, left: u32, right: u32) -> bool {
+        self.reachable(left).contains(&right)
+    }
+
+    // Every page a chain of one or more rules leads to from `page`
+    fn reachable(&self, page: u32) -> HashSet<u32> {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([page]);
+        while let Some(page) = queue.pop_front() {
+            for &next in self.after.get(&page).into_iter().flatten() {
+                if seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+
+    //
+    // The groups of pages that the rules order in a circle, so no order
+    // of all of them can satisfy every rule: the strongly connected
+    // components with more than one page (or a page that must come before
+    // itself).  Each group is in increasing order, and the groups are
+    // ordered by their first page.
+    //
+    pub fn cycles(&self) -> Vec<Vec<u32>> {
+        // Kosaraju's algorithm: order the pages by when a depth first
+        // search finishes with them, then search the reversed graph in
+        // the opposite order.  Each search of the reversed graph finds
+        // one component.
+        let pages = self.pages();
+        let mut finished = Vec::with_capacity(pages.len());
+        let mut seen = HashSet::new();
+        for &root in &pages {
+            if !seen.insert(root) {
+                continue;
+            }
+            let mut stack = vec![(root, 0)];
+            while let Some((page, next)) = stack.pop() {
+                let after = self.after.get(&page).map_or(&[][..], Vec::as_slice);
+                if let Some(&child) = after.get(next) {
+                    stack.push((page, next + 1));
+                    if seen.insert(child) {
+                        stack.push((child, 0));
+                    }
+                } else {
+                    finished.push(page);
+                }
+            }
+        }
+
+        let mut before = HashMap::<u32, Vec<u32>>::new();
+        for &(left, right) in &self.rules {
+            before.entry(right).or_default().push(left);
+        }
+        let mut assigned = HashSet::new();
+        let mut cycles = Vec::new();
+        for &root in finished.iter().rev() {
+            if !assigned.insert(root) {
+                continue;
+            }
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            while let Some(page) = stack.pop() {
+                for &prev in before.get(&page).into_iter().flatten() {
+                    if assigned.insert(prev) {
+                        component.push(prev);
+                        stack.push(prev);
+                    }
+                }
+            }
+            if component.len() > 1 || self.has_rule(root, root) {
+                component.sort_unstable();
+                cycles.push(component);
+            }
+        }
+        cycles.sort_unstable();
+        cycles
+    }
+
+    //
+    // Put the pages of an update in order.  Where there's a rule for a
+    // pair of pages, it's used; where there isn't, the pair is ordered if
+    // a chain of rules leads from one to the other (but not both ways).
+    // The result is a topological sort of the pages, choosing the page
+    // that appears earliest in the update whenever there's a choice, so
+    // an update that's already in order comes back unchanged.
+    //
+    // The chains of rules are followed with one search from each page of
+    // the update, the first time one of its pairs has no rule.
+    //
+    pub fn order(&self, pages: &[u32]) -> UpdateOrder {
+        let n = pages.len();
+        // before[j] is the positions of the pages that must come before pages[j]
+        let mut before = vec![Vec::new(); n];
+        let mut reachable: Vec<Option<HashSet<u32>>> = vec![None; n];
+        let mut inferred = false;
+        for (i, j) in (0..n).tuple_combinations() {
+            let (left, right) = (pages[i], pages[j]);
+            if left == right {
+                continue;
+            }
+            let (forward, backward) = match (self.has_rule(left, right), self.has_rule(right, left)) {
+                (false, false) => {
+                    inferred = true;
+                    let forward = reachable[i].get_or_insert_with(|| self.reachable(left)).contains(&right);
+                    let backward = reachable[j].get_or_insert_with(|| self.reachable(right)).contains(&left);
+                    match (forward, backward) {
+                        // Following the rules through other pages leads
+                        // both ways, so it doesn't order this pair
+                        (true, true) => (false, false),
+                        pair => pair,
+                    }
+                }
+                pair => pair,
+            };
+            if forward {
+                before[j].push(i);
+            }
+            if backward {
+                before[i].push(j);
+            }
+        }
+
+        let mut placed = vec![false; n];
+        let mut sorted = Vec::with_capacity(n);
+        while sorted.len() < n {
+            let next = (0..n).find(|&j| !placed[j] && before[j].iter().all(|&i| placed[i]));
+            let Some(next) = next else {
+                return UpdateOrder::Cyclic(find_cycle(&before, &placed).into_iter().map(|i| pages[i]).collect());
+            };
+            placed[next] = true;
+            sorted.push(pages[next]);
+        }
+        if inferred {
+            UpdateOrder::Inferred(sorted)
+        } else {
+            UpdateOrder::Total(sorted)
+        }
+    }
+}
+
+//
+// Every unplaced page has an unplaced page that must come before it, so
+// following those backwards from any of them must eventually repeat.
+// The pages from the first repeat on are a cycle, which is returned
+// starting from the page that appears earliest in the update.
+//
+fn find_cycle(before: &[Vec<usize>], placed: &[bool]) -> Vec<usize> {
+    let mut path = vec![placed.iter().position(|&placed| !placed).unwrap()];
+    loop {
+        let last = *path.last().unwrap();
+        let prev = *before[last].iter().find(|&&i| !placed[i]).unwrap();
+        if let Some(start) = path.iter().position(|&i| i == prev) {
+            // The path was built backwards, so reverse it to put each page
+            // before the one it must precede
+            let mut cycle = path.split_off(start);
+            cycle.reverse();
+            let first = cycle.iter().position_min().unwrap();
+            cycle.rotate_left(first);
+            return cycle;
+        }
+        path.push(prev);
+    }
+}
+
+//
+// Both parts, using `PrecedenceGraph::order`.  Unlike the other
+// variants, this doesn't need a rule for every pair of pages in an
+// update, and reports contradictory rules instead of panicking.
+//
+pub fn both_parts_graph(input: &str) -> Result<(u32, u32), AocError> {
+    let (rules, updates) = input.split_once("\n\n")
+        .ok_or_else(|| AocError::new("expected a blank line between the rules and the updates"))?;
+    let graph: PrecedenceGraph = rules.parse()?;
+    let rule_lines = rules.lines().count() + 1;
+
+    let mut part1 = 0;
+    let mut part2 = 0;
+    for (i, line) in updates.lines().enumerate() {
+        let line_number = rule_lines + i + 1;
+        let pages: Vec<u32> = line.split(',').map(str::parse).try_collect().on_line(line_number)?;
+        match graph.order(&pages) {
+            UpdateOrder::Cyclic(cycle) => {
+                return Err(AocError::at_line(line_number, format!("the rules for pages {} form a cycle", cycle.iter().join(", "))));
+            }
+            order => {
+                let sorted = order.pages().unwrap();
+                if sorted == pages {
+                    part1 += sorted[sorted.len() / 2];
+                } else {
+                    part2 += sorted[sorted.len() / 2];
+                }
+            }
+        }
+    }
+    Ok((part1, part2))
+}
+
+#[test]
+fn test_page_order() {
+    let order: PageOrder = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n".parse().unwrap();
+    assert_eq!(order.compare(&47, &53), Ordering::Less);
+    assert_eq!(order.compare(&53, &47), Ordering::Greater);
+    assert_eq!(order.compare(&47, &47), Ordering::Equal);
+    assert!(order.is_sorted(&[75, 47, 61, 53, 29]));
+    assert!(!order.is_sorted(&[75, 97, 47, 61, 53]));
+
+    let mut pages = [97, 13, 75, 29, 47];
+    assert_eq!(order.middle_page(&pages), Some(47));
+    order.sort(&mut pages);
+    assert_eq!(pages, [97, 75, 47, 29, 13]);
+    assert_eq!(order.middle_page(&[61, 13, 29]), Some(29));
+    assert_eq!(order.middle_page(&[75]), Some(75));
+    assert_eq!(order.middle_page(&[]), None);
+    // No rule for 1 and 2, so there's no telling which is in the middle
+    assert_eq!(order.middle_page(&[1, 2, 3]), None);
+
+    assert_eq!("47|53\n4753\n".parse::<PageOrder>().unwrap_err().to_string(), "line 2: expected a rule like 47|53, found \"4753\"");
+}
+
+#[test]
+fn test_both_parts_middle() {
+    let input = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47\n";
+    assert_eq!(both_parts_middle(input).unwrap(), (143, 123));
+    assert_eq!(both_parts_middle("1|2\n\n2,1,3\n").unwrap_err().to_string(), "line 3: some pages have no rule between them");
+}
+
+#[test]
+fn test_read_updates() {
+    let input = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47\n";
+    let mut updates = read_updates(input.as_bytes()).unwrap();
+    assert_eq!(updates.next(), Some(Ok(CheckedUpdate { line: 23, valid: true, pages: vec![75, 47, 61, 53, 29], middle: 61 })));
+    let updates: Vec<CheckedUpdate> = updates.try_collect().unwrap();
+    assert_eq!(updates.len(), 5);
+    assert_eq!(updates[2], CheckedUpdate { line: 26, valid: false, pages: vec![97, 75, 47, 61, 53], middle: 47 });
+    assert_eq!(both_parts_stream(input.as_bytes()), Ok((143, 123)));
+    assert_eq!(both_parts_stream(input.replace('\n', "\r\n").as_bytes()), Ok((143, 123)));
+
+    // Errors are reported as each update is read
+    let mut updates = read_updates("47|53\n\n47,53\n47,13\n53,x\n".as_bytes()).unwrap();
+    assert!(updates.next().unwrap().is_ok());
+    assert_eq!(updates.next().unwrap().unwrap_err().to_string(), "line 4: no rule orders pages 47 and 13");
+    assert_eq!(updates.next().unwrap().unwrap_err().line, Some(5));
+    assert_eq!(updates.next(), None);
+    assert_eq!(read_updates("47|53\n".as_bytes()).err().unwrap().to_string(), "expected a blank line between the rules and the updates");
+    assert_eq!(read_updates("47|53\n47\n\n".as_bytes()).err().unwrap().to_string(), "line 2: expected a rule like 47|53, found \"47\"");
+}
+
+#[test]
+fn test_precedence_graph() {
+    let rules = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n";
+    let graph: PrecedenceGraph = rules.parse().unwrap();
+    assert_eq!(graph.pages(), vec![13, 29, 47, 53, 61, 75, 97]);
+    assert_eq!(graph.cycles(), Vec::<Vec<u32>>::new());
+    assert_eq!(graph.order(&[75, 47, 61, 53, 29]), UpdateOrder::Total(vec![75, 47, 61, 53, 29]));
+    assert_eq!(graph.order(&[97, 13, 75, 29, 47]), UpdateOrder::Total(vec![97, 75, 47, 29, 13]));
+
+    // 1 and 3 have no rule between them, but 2 is between them; 4 and 5
+    // aren't related at all, so they stay in the update's order
+    let graph = PrecedenceGraph::from_rules([(1, 2), (2, 3), (5, 6)]);
+    assert_eq!(graph.order(&[3, 1]), UpdateOrder::Inferred(vec![1, 3]));
+    assert_eq!(graph.order(&[5, 4, 3, 1]), UpdateOrder::Inferred(vec![5, 4, 1, 3]));
+    assert!(graph.reaches(1, 3));
+    assert!(!graph.reaches(3, 1));
+
+    // Cycles, including a page that must come before itself
+    let graph = PrecedenceGraph::from_rules([(1, 2), (2, 3), (3, 1), (3, 4), (5, 5), (6, 7), (7, 6)]);
+    assert_eq!(graph.cycles(), vec![vec![1, 2, 3], vec![5], vec![6, 7]]);
+    assert_eq!(graph.order(&[4, 2, 3, 1]), UpdateOrder::Cyclic(vec![2, 3, 1]));
+    assert_eq!(graph.order(&[3, 4, 7, 6]), UpdateOrder::Cyclic(vec![7, 6]));
+    // The rules as a whole have cycles, but the chains of rules from 1
+    // and 2 to 4 only lead one way, so those pairs are still ordered
+    assert_eq!(graph.order(&[4, 1]), UpdateOrder::Inferred(vec![1, 4]));
+    assert_eq!(graph.order(&[4, 6, 2, 1]), UpdateOrder::Inferred(vec![6, 1, 2, 4]));
+    // 1 and 3 have no rule, and chains of rules lead both ways between
+    // them, which doesn't order the pair
+    let graph = PrecedenceGraph::from_rules([(1, 2), (2, 3), (3, 4), (4, 1)]);
+    assert_eq!(graph.order(&[3, 1]), UpdateOrder::Inferred(vec![3, 1]));
+    assert_eq!(graph.order(&[1, 3]), UpdateOrder::Inferred(vec![1, 3]));
+}
+
+#[test]
+fn test_both_parts_graph() {
+    let input = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+    assert_eq!(both_parts_graph(input), Ok((143, 123)));
+    // The other variants need a rule for 47 and 13
+    assert_eq!(both_parts_graph("47|53\n53|13\n\n47,13,53\n13,53,47\n"), Ok((0, 53 + 53)));
+    assert_eq!(both_parts_graph("47|53\n53|13\n\n47,53,13\n"), Ok((53, 0)));
+    let err = both_parts_graph("47|53\n53|13\n13|47\n\n13,53,47\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 5: the rules for pages 13, 47, 53 form a cycle");
+    assert!(both_parts_graph("47|53\n47-13\n\n47,53\n").is_err());
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = try_part1("47|53\n75,47\n").unwrap_err();
+    assert_eq!(err.to_string(), "expected a blank line between the rules and the updates");
+    let err = try_part1("47|53\n47-13\n\n47,53\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 2: expected a rule like 47|53, found \"47-13\"");
+    let err = try_part2("47|53\n\n47,53\n47,x\n").unwrap_err();
+    assert_eq!(err.line, Some(4));
+    let err = try_part2("47|53\n\n47,53,13\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 3: no rule orders pages 47 and 13");
+    assert_eq!(try_part1("47|53\n\n47,53,47\n"), Err(AocError::at_line(3, "page 47 appears more than once")));
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+    aoc_utils::assert_variants_agree::<Day05>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(5);
+    aoc_utils::assert_variants_agree::<Day05>(&input);
+}
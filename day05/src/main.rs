@@ -1,9 +1,44 @@
-use day05::both_parts;
+use aoc_utils::load_input_or_exit;
+use day05::{both_parts, read_updates};
 
+//
+// With --stream, read the input from stdin one update at a time, and
+// print each update's result as it's checked, then the answers.
+//
 fn main() {
-    let input = include_str!("../input.txt");
+    if std::env::args().nth(1).as_deref() == Some("--stream") {
+        stream();
+        return;
+    }
 
-    let (result1, result2) = both_parts(input);
+    let input = load_input_or_exit(5);
+
+    let (result1, result2) = both_parts(&input);
+    println!("Part 1: {result1}");
+    println!("Part 2: {result2}");
+}
+
+fn stream() {
+    let updates = read_updates(std::io::stdin().lock()).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+    let mut result1 = 0;
+    let mut result2 = 0;
+    for update in updates {
+        let update = update.unwrap_or_else(|err| {
+            eprintln!("{err}");
+            std::process::exit(1);
+        });
+        if update.valid {
+            println!("line {}: valid, middle page {}", update.line, update.middle);
+            result1 += update.middle;
+        } else {
+            let pages: Vec<String> = update.pages.iter().map(u32::to_string).collect();
+            println!("line {}: reordered to {}, middle page {}", update.line, pages.join(","), update.middle);
+            result2 += update.middle;
+        }
+    }
     println!("Part 1: {result1}");
     println!("Part 2: {result2}");
 }
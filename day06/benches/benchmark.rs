@@ -1,27 +1,98 @@
-use day06::{ part1, part2, both_parts, both_parts_cached };
-
+//
+// Generated maps of different sizes.  The guard's walk on them is much
+// shorter than on the real map, which favours the variants with no
+// setup cost over both_parts_jump.  With `--features rayon`, both also
+// compare both_parts_jump_parallel on different numbers of threads.
+//
 fn main() {
-    divan::main();
+    aoc_utils::bench::main(6);
 }
 
-const INPUT: &str = include_str!("../input.txt");
+#[cfg(feature = "rayon")]
+const THREADS: [usize; 4] = [1, 2, 4, 8];
 
-#[divan::bench]
-fn bench_part1() {
-    part1(INPUT);
+#[cfg(feature = "rayon")]
+fn pool(threads: usize) -> rayon::ThreadPool {
+    rayon::ThreadPoolBuilder::new().num_threads(threads).build().expect("thread pool")
 }
 
-#[divan::bench]
-fn bench_part2() {
-    part2(INPUT);
-}
+mod full_input {
+    use std::sync::LazyLock;
+
+    use aoc_utils::load_input;
+    use day06::{ part1, part2, both_parts, both_parts_cached, both_parts_jump, both_parts_simulator };
+
+    static INPUT: LazyLock<String> = LazyLock::new(|| load_input(6).expect("input"));
+
+    #[divan::bench]
+    fn bench_part1() {
+        part1(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_part2() {
+        part2(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts() {
+        both_parts(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts_cached() {
+        both_parts_cached(&INPUT);
+    }
 
-#[divan::bench]
-fn bench_both_parts() {
-    both_parts(INPUT);
+    #[divan::bench]
+    fn bench_both_parts_jump() {
+        both_parts_jump(&INPUT);
+    }
+
+    #[divan::bench]
+    fn bench_both_parts_simulator() {
+        both_parts_simulator(&INPUT).unwrap();
+    }
+
+    #[cfg(feature = "rayon")]
+    #[divan::bench(args = super::THREADS)]
+    fn bench_both_parts_jump_parallel(bencher: divan::Bencher, threads: usize) {
+        let pool = super::pool(threads);
+        bencher.bench(|| pool.install(|| day06::both_parts_jump_parallel(&INPUT)));
+    }
 }
 
-#[divan::bench]
-fn bench_both_parts_cached() {
-    both_parts_cached(INPUT);
+mod generated {
+    use aoc_utils::bench::generated;
+    use divan::Bencher;
+    use day06::{ both_parts, both_parts_cached, both_parts_jump };
+
+    // Side of the square map (the real one is 130)
+    const SIZES: &[usize] = &[65, 130, 260];
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts(bencher: Bencher, size: usize) {
+        let input = generated(6, size);
+        bencher.bench(|| both_parts(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_cached(bencher: Bencher, size: usize) {
+        let input = generated(6, size);
+        bencher.bench(|| both_parts_cached(&input));
+    }
+
+    #[divan::bench(args = SIZES)]
+    fn bench_both_parts_jump(bencher: Bencher, size: usize) {
+        let input = generated(6, size);
+        bencher.bench(|| both_parts_jump(&input));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[divan::bench(consts = super::THREADS, args = SIZES)]
+    fn bench_both_parts_jump_parallel<const THREADS: usize>(bencher: Bencher, size: usize) {
+        let input = generated(6, size);
+        let pool = super::pool(THREADS);
+        bencher.bench(|| pool.install(|| day06::both_parts_jump_parallel(&input)));
+    }
 }
@@ -1,4 +1,55 @@
 use rustc_hash::{FxHashMap, FxHashSet};
+use aoc_utils::{AocError, Grid, Solution, Variant};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("cached", |input| check_map(input).map(|_| both_parts_cached(input).0)),
+        Variant::fallible("default", try_part1),
+        Variant::new("both_parts", |input| both_parts(input).0),
+        Variant::fallible("jump", |input| check_map(input).map(|_| both_parts_jump(input).0)),
+        #[cfg(feature = "rayon")]
+        Variant::fallible("jump_parallel", |input| check_map(input).map(|_| both_parts_jump_parallel(input).0)),
+        Variant::fallible("simulator", |input| both_parts_simulator(input).map(|(part1, _)| part1)),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("cached", |input| check_map(input).map(|_| both_parts_cached(input).1)),
+        Variant::fallible("default", try_part2),
+        Variant::new("both_parts", |input| both_parts(input).1),
+        Variant::fallible("jump", |input| check_map(input).map(|_| both_parts_jump(input).1)),
+        #[cfg(feature = "rayon")]
+        Variant::fallible("jump_parallel", |input| check_map(input).map(|_| both_parts_jump_parallel(input).1)),
+        Variant::fallible("simulator", |input| both_parts_simulator(input).map(|(_, part2)| part2)),
+    ];
+}
+
+//
+// The map must be rectangular, contain only open squares and obstacles,
+// and have exactly one guard (who starts out facing up).  Returns the
+// parsed map, for callers that want it.
+//
+fn check_map(input: &str) -> Result<Grid<u8>, AocError> {
+    let grid = Grid::parse_checked(input, |ch| ".#^".contains(ch))?;
+    match grid.find_all(&b'^').count() {
+        0 => Err(AocError::new("no guard (^) in map")),
+        1 => Ok(grid),
+        n => Err(AocError::new(format!("expected one guard, found {n}"))),
+    }
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part2(input))
+}
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Direction {
@@ -182,7 +233,7 @@ pub fn part2(input: &str) -> usize {
 mod both_parts {
     use super::Direction;
     use rustc_hash::{FxHashSet, FxHashMap};
-    use ndarray::{Array, Dim};
+    use ndarray::{Array, Axis, Dim};
 
     type Row = usize;
     type Col = usize;
@@ -570,9 +621,504 @@ mod both_parts {
 
         (part1, part2)
     }
+
+    // The square one step away in the given direction
+    fn ahead((row, col): (Row, Col), facing: Direction) -> (Row, Col) {
+        match facing {
+            Direction::Up => (row - 1, col),
+            Direction::Right => (row, col + 1),
+            Direction::Down => (row + 1, col),
+            Direction::Left => (row, col - 1)
+        }
+    }
+
+    const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    //
+    // Where the guard ends up after going straight as far as possible:
+    // the last square before an obstacle or the edge, and which of those
+    // stopped them.
+    //
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Jump {
+        row: u32,
+        col: u32,
+        kind: GridSquare,
+    }
+
+    impl Jump {
+        fn position(&self) -> (Row, Col) {
+            (self.row as Row, self.col as Col)
+        }
+    }
+
+    //
+    // The Jump from every open square, in every direction, so that going
+    // straight is a single lookup.  Adding an obstacle only changes the
+    // jumps that would have run through it: the squares behind it on
+    // each side, up to the next obstacle.  So `patch` changes just those,
+    // and `restore` puts them back, instead of rebuilding the table for
+    // each new obstacle.
+    //
+    struct JumpTable {
+        jumps: Array<Jump, Dim<[usize; 3]>>,
+        patched: Vec<((usize, Row, Col), Jump)>,
+    }
+
+    impl JumpTable {
+        fn new(grid: &Grid) -> JumpTable {
+            let (rows, cols) = grid.dim();
+            let mut jumps = Array::from_elem((4, rows, cols), Jump { row: 0, col: 0, kind: GridSquare::OutOfBounds });
+            for facing in DIRECTIONS {
+                // Scan each line away from the direction the guard is
+                // facing, remembering where the last obstacle (or the
+                // edge) would stop them
+                let mut jumps = jumps.index_axis_mut(Axis(0), facing as usize);
+                let (lines, length) = match facing {
+                    Direction::Up | Direction::Down => (cols - 2, rows - 2),
+                    Direction::Left | Direction::Right => (rows - 2, cols - 2),
+                };
+                for line in 1..=lines {
+                    let mut stop = None;
+                    for i in 1..=length {
+                        let pos = match facing {
+                            Direction::Up => (i, line),
+                            Direction::Down => (rows - 1 - i, line),
+                            Direction::Left => (line, i),
+                            Direction::Right => (line, cols - 1 - i),
+                        };
+                        if grid[pos] != GridSquare::Open {
+                            stop = None;
+                            continue;
+                        }
+                        let next = ahead(pos, facing);
+                        if grid[next] != GridSquare::Open {
+                            stop = Some(Jump { row: pos.0 as u32, col: pos.1 as u32, kind: grid[next] });
+                        }
+                        jumps[pos] = stop.unwrap();
+                    }
+                }
+            }
+            JumpTable { jumps, patched: Vec::new() }
+        }
+
+        fn jump(&self, (row, col): (Row, Col), facing: Direction) -> Jump {
+            self.jumps[(facing as usize, row, col)]
+        }
+
+        //
+        // Like `jump`, but as if there were also an obstacle at `obstacle`
+        // (which must be open).  This leaves the table alone, so it can be
+        // shared between threads trying different obstacles.
+        //
+        #[cfg(feature = "rayon")]
+        fn jump_past(&self, pos: (Row, Col), facing: Direction, obstacle: (Row, Col)) -> Jump {
+            let jump = self.jump(pos, facing);
+            let end = jump.position();
+            let blocked = match facing {
+                Direction::Up => obstacle.1 == pos.1 && (end.0..pos.0).contains(&obstacle.0),
+                Direction::Down => obstacle.1 == pos.1 && (pos.0+1..=end.0).contains(&obstacle.0),
+                Direction::Left => obstacle.0 == pos.0 && (end.1..pos.1).contains(&obstacle.1),
+                Direction::Right => obstacle.0 == pos.0 && (pos.1+1..=end.1).contains(&obstacle.1),
+            };
+            if blocked {
+                let mut backwards = facing;
+                backwards.turn();
+                backwards.turn();
+                let stop = ahead(obstacle, backwards);
+                Jump { row: stop.0 as u32, col: stop.1 as u32, kind: GridSquare::Obstacle }
+            } else {
+                jump
+            }
+        }
+
+        //
+        // Pretend there's an obstacle at `obstacle` (which must be open).
+        //
+        fn patch(&mut self, grid: &Grid, obstacle: (Row, Col)) {
+            for facing in DIRECTIONS {
+                // Walk backwards from the obstacle; every open square
+                // there now stops next to it
+                let mut backwards = facing;
+                backwards.turn();
+                backwards.turn();
+                let stop = ahead(obstacle, backwards);
+                let jump = Jump { row: stop.0 as u32, col: stop.1 as u32, kind: GridSquare::Obstacle };
+                let mut pos = stop;
+                while grid[pos] == GridSquare::Open {
+                    let index = (facing as usize, pos.0, pos.1);
+                    self.patched.push((index, self.jumps[index]));
+                    self.jumps[index] = jump;
+                    pos = ahead(pos, backwards);
+                }
+            }
+        }
+
+        fn restore(&mut self) {
+            for (index, jump) in self.patched.drain(..) {
+                self.jumps[index] = jump;
+            }
+        }
+    }
+
+    // Squares on the guard's path, and the direction they first went in
+    type Path = Vec<((Row, Col), Direction)>;
+
+    //
+    // Parse the map (with a border of OutOfBounds squares), build its
+    // JumpTable, and follow the guard's path: every square they visit
+    // after the start, with the direction they were facing when they
+    // first got there.
+    //
+    fn jump_path(input: &str) -> (Grid, JumpTable, Path) {
+        let num_rows = input.lines().count();
+        let num_cols = input.lines().next().unwrap().len();
+
+        // Build a 2D array from the input, with extra rows and columns
+        // on each edge set to OutOfBounds.
+        let mut grid = Array::from_elem((num_rows+2, num_cols+2), GridSquare::OutOfBounds);
+        let mut start: Option<(usize, usize)> = None;
+        for (row, line) in input.lines().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                grid[(row+1, col+1)] = match ch {
+                    '.' => GridSquare::Open,
+                    '#' => GridSquare::Obstacle,
+                    '^' => {
+                        start = Some((row+1, col+1));
+                        GridSquare::Open
+                    }
+                    other => panic!("Invalid input: {}", other)
+                };
+            }
+        }
+        let start = start.unwrap();
+        let table = JumpTable::new(&grid);
+
+        // Part 1: follow the jumps, recording the direction the guard
+        // was facing when they first reached each square.
+        let mut first_facing = Array::<Option<Direction>, _>::from_elem(grid.dim(), None);
+        let mut candidates = Vec::new();
+        let mut pos = start;
+        let mut facing = Direction::Up;
+        first_facing[pos] = Some(facing);
+        loop {
+            let jump = table.jump(pos, facing);
+            while pos != jump.position() {
+                pos = ahead(pos, facing);
+                if first_facing[pos].is_none() {
+                    first_facing[pos] = Some(facing);
+                    candidates.push((pos, facing));
+                }
+            }
+            if jump.kind == GridSquare::OutOfBounds {
+                break;
+            }
+            facing.turn();
+        }
+        (grid, table, candidates)
+    }
+
+    //
+    // Both parts, going straight with a JumpTable.  For part 2, each new
+    // obstacle is patched into the table, and the guard starts just
+    // before it.  Loops are found by marking the states seen at each
+    // turn with the number of the obstacle being tried, so nothing needs
+    // to be cleared between obstacles.
+    //
+    // Building the table touches every square four times, so this only
+    // pays off when the guard's walk is long compared to the map.
+    //
+    pub fn both_parts_jump(input: &str) -> (usize, usize) {
+        let (grid, mut table, candidates) = jump_path(input);
+        let part1 = candidates.len() + 1;
+
+        // Part 2: try an obstacle at each square on the path, other than
+        // the start.
+        let mut seen = Array::<u32, _>::zeros((grid.dim().0, grid.dim().1, 4));
+        let mut part2 = 0;
+        for (stamp, &(obstacle, facing)) in (1..).zip(&candidates) {
+            table.patch(&grid, obstacle);
+            let mut facing = facing;
+            let mut backwards = facing;
+            backwards.turn();
+            backwards.turn();
+            let mut pos = ahead(obstacle, backwards);
+            loop {
+                let jump = table.jump(pos, facing);
+                if jump.kind == GridSquare::OutOfBounds {
+                    break;
+                }
+                pos = jump.position();
+                facing.turn();
+                let state = &mut seen[(pos.0, pos.1, facing as usize)];
+                if *state == stamp {
+                    part2 += 1;
+                    break;
+                }
+                *state = stamp;
+            }
+            table.restore();
+        }
+
+        (part1, part2)
+    }
+
+    //
+    // Like both_parts_jump, but trying the obstacles for part 2 in
+    // parallel, on however many threads the current rayon pool has.
+    // The grid and JumpTable are shared by all the threads, so the new
+    // obstacle is accounted for by `jump_past` instead of patching the
+    // table.  Each thread gets its own `seen` array (and stamps).
+    //
+    #[cfg(feature = "rayon")]
+    pub fn both_parts_jump_parallel(input: &str) -> (usize, usize) {
+        use rayon::prelude::*;
+
+        let (grid, table, candidates) = jump_path(input);
+        let part1 = candidates.len() + 1;
+
+        let part2 = candidates.par_iter()
+            .map_init(
+                || (Array::<u32, _>::zeros((grid.dim().0, grid.dim().1, 4)), 0),
+                |(seen, stamp), &(obstacle, facing)| {
+                    *stamp += 1;
+                    let mut facing = facing;
+                    let mut backwards = facing;
+                    backwards.turn();
+                    backwards.turn();
+                    let mut pos = ahead(obstacle, backwards);
+                    loop {
+                        let jump = table.jump_past(pos, facing, obstacle);
+                        if jump.kind == GridSquare::OutOfBounds {
+                            return false;
+                        }
+                        pos = jump.position();
+                        facing.turn();
+                        let state = &mut seen[(pos.0, pos.1, facing as usize)];
+                        if *state == *stamp {
+                            return true;
+                        }
+                        *state = *stamp;
+                    }
+                })
+            .filter(|&loops| loops)
+            .count();
+
+        (part1, part2)
+    }
+}
+
+pub use both_parts::{both_parts, both_parts_cached, both_parts_jump};
+#[cfg(feature = "rayon")]
+pub use both_parts::both_parts_jump_parallel;
+
+//
+// A public simulator, for looking at how the guard moves and why an
+// obstruction traps them, rather than just counting.
+//
+mod simulator {
+    use std::str::FromStr;
+    use rustc_hash::{FxHashMap, FxHashSet};
+    use aoc_utils::{AocError, Direction, Grid};
+    use aoc_utils::grid::Pos;
+
+    //
+    // The lab's map, and where the guard starts (facing North).
+    //
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Lab {
+        grid: Grid<u8>,
+        start: Pos,
+    }
+
+    //
+    // The guard walking straight: from `start`, `length` steps in
+    // `direction`, until they were stopped by an obstacle (which they
+    // turn right at), or walked off the map.  A segment can have length
+    // 0, when the guard turns twice in the same place.
+    //
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Segment {
+        pub start: Pos,
+        pub direction: Direction,
+        pub length: usize,
+        pub stop: Stop,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Stop {
+        Obstacle(Pos),
+        Exit,
+    }
+
+    //
+    // Proof that the guard is stuck in a loop: they started a segment at
+    // `position` facing `direction` before, and since they always do the
+    // same thing from the same state, they'll keep repeating the last
+    // `cycle_segments` segments, which are `cycle_length` steps (turns
+    // aren't steps).
+    //
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct LoopCertificate {
+        pub position: Pos,
+        pub direction: Direction,
+        pub cycle_length: usize,
+        pub cycle_segments: usize,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Outcome {
+        Exits,
+        Loops(LoopCertificate),
+    }
+
+    //
+    // Everything the guard did: their segments up until they leave, or
+    // until they're about to repeat themselves.
+    //
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Patrol {
+        pub segments: Vec<Segment>,
+        pub outcome: Outcome,
+    }
+
+    impl Segment {
+        // The positions the guard occupies, including the start
+        pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+            let (dr, dc) = self.direction.delta();
+            (0..=self.length as isize).map(move |k| {
+                (self.start.0.wrapping_add_signed(dr * k), self.start.1.wrapping_add_signed(dc * k))
+            })
+        }
+
+        pub fn end(&self) -> Pos {
+            self.positions().last().unwrap()
+        }
+    }
+
+    impl Patrol {
+        pub fn visited(&self) -> FxHashSet<Pos> {
+            self.segments.iter().flat_map(Segment::positions).collect()
+        }
+    }
+
+    impl FromStr for Lab {
+        type Err = AocError;
+
+        fn from_str(input: &str) -> Result<Self, AocError> {
+            let mut grid = super::check_map(input)?;
+            let start = grid.find(&b'^').unwrap();
+            grid[start] = b'.';
+            Ok(Lab { grid, start })
+        }
+    }
+
+    impl Lab {
+        pub fn start(&self) -> Pos {
+            self.start
+        }
+
+        pub fn grid(&self) -> &Grid<u8> {
+            &self.grid
+        }
+
+        //
+        // Follow the guard, with an optional extra obstruction, until
+        // they leave the map or get stuck in a loop.
+        //
+        pub fn patrol(&self, obstruction: Option<Pos>) -> Patrol {
+            let mut segments = Vec::new();
+            // The segment and step count at which each state was first seen
+            let mut seen = FxHashMap::<(Pos, Direction), (usize, usize)>::default();
+            let mut pos = self.start;
+            let mut facing = Direction::North;
+            let mut steps = 0;
+            loop {
+                if let Some(&(segment, at_steps)) = seen.get(&(pos, facing)) {
+                    let certificate = LoopCertificate {
+                        position: pos,
+                        direction: facing,
+                        cycle_length: steps - at_steps,
+                        cycle_segments: segments.len() - segment,
+                    };
+                    return Patrol { segments, outcome: Outcome::Loops(certificate) };
+                }
+                seen.insert((pos, facing), (segments.len(), steps));
+
+                let mut end = pos;
+                let mut length = 0;
+                let stop = loop {
+                    match self.grid.offset(end, facing.delta()) {
+                        None => break Stop::Exit,
+                        Some(next) if self.grid[next] == b'#' || Some(next) == obstruction => break Stop::Obstacle(next),
+                        Some(next) => { end = next; length += 1; }
+                    }
+                };
+                segments.push(Segment { start: pos, direction: facing, length, stop });
+                steps += length;
+                if stop == Stop::Exit {
+                    return Patrol { segments, outcome: Outcome::Exits };
+                }
+                pos = end;
+                facing = facing.turn_right();
+            }
+        }
+
+        //
+        // Every obstruction that traps the guard, with the proof.  Only
+        // the squares on the guard's original path (except where they
+        // start) can change where they go.
+        //
+        pub fn loop_certificates(&self) -> Vec<(Pos, LoopCertificate)> {
+            let mut candidates: Vec<Pos> = self.patrol(None).visited().into_iter().filter(|&pos| pos != self.start).collect();
+            candidates.sort_unstable();
+            candidates.into_iter()
+                .filter_map(|obstruction| match self.patrol(Some(obstruction)).outcome {
+                    Outcome::Loops(certificate) => Some((obstruction, certificate)),
+                    Outcome::Exits => None,
+                })
+                .collect()
+        }
+
+        //
+        // Draw the patrol the way the puzzle does: "|" and "-" where the
+        // guard walked up/down or left/right, "+" where they did both,
+        // "O" for the extra obstruction, and "^" where they started.
+        //
+        pub fn render(&self, patrol: &Patrol, obstruction: Option<Pos>) -> String {
+            let mut picture = self.grid.map(|&cell| cell as char);
+            for segment in &patrol.segments {
+                let mark = match segment.direction {
+                    Direction::North | Direction::South => '|',
+                    Direction::East | Direction::West => '-',
+                };
+                for pos in segment.positions() {
+                    picture[pos] = match picture[pos] {
+                        '.' => mark,
+                        ch if ch == mark => mark,
+                        _ => '+',
+                    };
+                }
+                // The guard turned here
+                if let Stop::Obstacle(_) = segment.stop {
+                    picture[segment.end()] = '+';
+                }
+            }
+            if let Some(obstruction) = obstruction {
+                picture[obstruction] = 'O';
+            }
+            picture[self.start] = '^';
+            picture.iter_rows().map(|row| row.iter().collect::<String>() + "\n").collect()
+        }
+    }
+
+    pub fn both_parts_simulator(input: &str) -> Result<(usize, usize), AocError> {
+        let lab: Lab = input.parse()?;
+        Ok((lab.patrol(None).visited().len(), lab.loop_certificates().len()))
+    }
 }
 
-pub use both_parts::{both_parts, both_parts_cached};
+pub use simulator::{both_parts_simulator, Lab, LoopCertificate, Outcome, Patrol, Segment, Stop};
 
 #[test]
 fn test_part1() {
@@ -643,26 +1189,161 @@ fn test_both_parts_WIP() {
     assert_eq!(both_parts_cached(input), (41, 6));
 }
 
-#[cfg(test)]
-const INPUT: &str = include_str!("../input.txt");
+#[test]
+fn test_simulator() {
+    use aoc_utils::Direction::*;
+    let input = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    let lab: Lab = input.parse().unwrap();
+    assert_eq!(both_parts_simulator(input), Ok((41, 6)));
+
+    let patrol = lab.patrol(None);
+    assert_eq!(patrol.outcome, Outcome::Exits);
+    assert_eq!(patrol.segments[..2], [
+        Segment { start: (6, 4), direction: North, length: 5, stop: Stop::Obstacle((0, 4)) },
+        Segment { start: (1, 4), direction: East, length: 4, stop: Stop::Obstacle((1, 9)) },
+    ]);
+    assert_eq!(patrol.segments.last(), Some(&Segment { start: (7, 7), direction: South, length: 2, stop: Stop::Exit }));
+
+    // The first of the puzzle's six obstructions
+    let certificates = lab.loop_certificates();
+    let obstructions: Vec<_> = certificates.iter().map(|&(pos, _)| pos).collect();
+    assert_eq!(obstructions, vec![(6, 3), (7, 6), (7, 7), (8, 1), (8, 3), (9, 7)]);
+    assert_eq!(certificates[0].1, LoopCertificate { position: (6, 4), direction: North, cycle_length: 18, cycle_segments: 4 });
+    let patrol = lab.patrol(Some((6, 3)));
+    assert_eq!(patrol.outcome, Outcome::Loops(certificates[0].1));
+    assert_eq!(lab.render(&patrol, Some((6, 3))), "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+");
+
+    // Turning twice in place is a segment of length 0
+    let lab: Lab = ".#.\n.^#\n...\n".parse().unwrap();
+    let patrol = lab.patrol(None);
+    assert_eq!(patrol.segments[1], Segment { start: (1, 1), direction: East, length: 0, stop: Stop::Obstacle((1, 2)) });
+    assert_eq!(patrol.outcome, Outcome::Exits);
+    assert!("...\n.#.\n".parse::<Lab>().is_err());
+}
+
+#[test]
+fn test_both_parts_jump() {
+    let input = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    assert_eq!(both_parts_jump(input), (41, 6));
+    // Turning around in a dead end, and an obstacle right in front
+    assert_eq!(both_parts_jump("#.#\n#^#\n...\n"), both_parts("#.#\n#^#\n...\n"));
+    assert_eq!(both_parts_jump(".#.\n.^.\n...\n"), both_parts(".#.\n.^.\n...\n"));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_both_parts_jump_parallel() {
+    let input = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    assert_eq!(both_parts_jump_parallel(input), (41, 6));
+    for input in ["#.#\n#^#\n...\n", ".#.\n.^.\n...\n", "..#.\n#...\n.^#.\n....\n"] {
+        assert_eq!(both_parts_jump_parallel(input), both_parts(input));
+    }
+}
+
+#[test]
+fn test_both_parts_jump_full() {
+    let input = aoc_utils::full_input!(6);
+    assert_eq!(both_parts_jump(&input), (5461, 1836));
+}
 
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(INPUT), 5461);
+    let input = aoc_utils::full_input!(6);
+    assert_eq!(part1(&input), 5461);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(INPUT), 1836);
+    let input = aoc_utils::full_input!(6);
+    assert_eq!(part2(&input), 1836);
 }
 
 #[test]
 fn test_both_parts_full() {
-    assert_eq!(both_parts(INPUT), (5461, 1836));
+    let input = aoc_utils::full_input!(6);
+    assert_eq!(both_parts(&input), (5461, 1836));
 }
 
 #[test]
 #[allow(non_snake_case)]
 fn test_both_parts_WIP_full() {
-    assert_eq!(both_parts_cached(INPUT), (5461, 1836));
+    let input = aoc_utils::full_input!(6);
+    assert_eq!(both_parts_cached(&input), (5461, 1836));
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = try_part1("..#\n.^\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 2: expected 3 columns, found 2");
+    let err = try_part1("..#\n.^x\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 2, column 3: unexpected 'x'");
+    assert_eq!(try_part2("..#\n...\n"), Err(AocError::new("no guard (^) in map")));
+    assert_eq!(try_part2("..^\n.^.\n"), Err(AocError::new("expected one guard, found 2")));
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+    aoc_utils::assert_variants_agree::<Day06>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(6);
+    aoc_utils::assert_variants_agree::<Day06>(&input);
 }
@@ -1,15 +1,58 @@
-use day06::both_parts_cached;
+use aoc_utils::load_input_or_exit;
+use day06::{both_parts_cached, Lab, Stop};
 
+//
+// With --trace, print the guard's path one segment at a time.  With
+// --loops, print each obstruction that traps the guard, with the proof,
+// and a picture of the loop.
+//
 fn main() {
-    let input = include_str!("../input.txt");
+    let input = load_input_or_exit(6);
 
-    // let result1 = part1(input);
+    match std::env::args().nth(1).as_deref() {
+        Some("--trace") => return trace(&input),
+        Some("--loops") => return loops(&input),
+        _ => {}
+    }
+
+    // let result1 = part1(&input);
     // println!("Part 1: {result1}");
 
-    // let result2 = part2(input);
+    // let result2 = part2(&input);
     // println!("Part 2: {result2}");
 
-    let (result1, result2) = both_parts_cached(input);
+    let (result1, result2) = both_parts_cached(&input);
     println!("Part 1: {result1}");
     println!("Part 2: {result2}");
 }
+
+fn lab(input: &str) -> Lab {
+    input.parse().unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    })
+}
+
+fn trace(input: &str) {
+    let lab = lab(input);
+    for segment in lab.patrol(None).segments {
+        let stop = match segment.stop {
+            Stop::Obstacle((row, col)) => format!("obstacle at {row},{col}"),
+            Stop::Exit => "edge of the map".to_string(),
+        };
+        let (row, col) = segment.start;
+        println!("{row},{col} {:?} {} steps, to {stop}", segment.direction, segment.length);
+    }
+}
+
+fn loops(input: &str) {
+    let lab = lab(input);
+    for (obstruction, certificate) in lab.loop_certificates() {
+        let (row, col) = obstruction;
+        let (loop_row, loop_col) = certificate.position;
+        println!("Obstruction at {row},{col}: back at {loop_row},{loop_col} facing {:?}, every {} steps ({} segments)",
+            certificate.direction, certificate.cycle_length, certificate.cycle_segments);
+        print!("{}", lab.render(&lab.patrol(Some(obstruction)), Some(obstruction)));
+        println!();
+    }
+}
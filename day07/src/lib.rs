@@ -1,5 +1,20 @@
 use itertools::{repeat_n, Itertools};
 use nom::{bytes::complete::tag, character::complete::u64 as parse_u64, multi::separated_list1, sequence::separated_pair, IResult};
+use aoc_utils::{AocError, NomResultExt, Solution, Variant};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    type Answer1 = u64;
+    type Answer2 = u64;
+    const PART1: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
 
 fn parse_operands(input: &str) -> IResult<&str, Vec<u64>> {
     separated_list1(tag(" "), parse_u64)(input)
@@ -9,6 +24,21 @@ fn parse_line(input: &str) -> IResult<&str, (u64, Vec<u64>)> {
     separated_pair(parse_u64, tag(": "), parse_operands)(input)
 }
 
+type Equation = (u64, Vec<u64>);
+
+fn parse_equations(input: &str) -> Result<Vec<Equation>, AocError> {
+    input.lines().enumerate().map(|(i, line)| {
+        // Errors are located within `line`, which is line 1 as far as
+        // they know.
+        let on_this_line = |err: AocError| AocError { line: Some(i + 1), ..err };
+        let (remaining, equation) = parse_line(line).located_in(line).map_err(on_this_line)?;
+        if !remaining.is_empty() {
+            return Err(on_this_line(AocError::at(line, remaining, "unexpected text after operands")));
+        }
+        Ok(equation)
+    }).collect()
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Operator { Add, Multiply, Concat }
 
@@ -44,10 +74,13 @@ fn try_eval(expected: u64, operands: &[u64], operators: &[Operator]) -> bool {
 }
 
 pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str) -> Result<u64, AocError> {
     use Operator::*;
 
-    input.lines().filter_map(|line| {
-        let (_remaining, (result, operands)) = parse_line(line).unwrap();
+    Ok(parse_equations(input)?.into_iter().filter_map(|(result, operands)| {
         for operators in repeat_n([Add, Multiply], operands.len()-1).multi_cartesian_product() {
             let temp = operands[1..].iter().zip(operators).fold(operands[0], |acc, (operand, operator)| {
                 match operator {
@@ -61,28 +94,30 @@ pub fn part1(input: &str) -> u64 {
             }
         }
         None
-    }).sum()
+    }).sum())
 }
 
 pub fn part2(input: &str) -> u64 {
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part2(input: &str) -> Result<u64, AocError> {
     use Operator::*;
 
-    input.lines().filter_map(|line| {
-        let (_remaining, (result, operands)) = parse_line(line).unwrap();
+    Ok(parse_equations(input)?.into_iter().filter_map(|(result, operands)| {
         for operators in repeat_n([Add, Multiply, Concat], operands.len()-1).multi_cartesian_product() {
             if try_eval(result, &operands, &operators) {
                 return Some(result);
             }
         }
         None
-    }).sum()
+    }).sum())
 }
 
 #[cfg(test)]
 mod test {
-    use super::{part1, part2};
+    use super::{part1, part2, try_part1, try_part2};
 
-    const FULL_INPUT: &str = include_str!("../input.txt");
     const EXAMPLE_INPUT: &str = "\
 190: 10 19
 3267: 81 40 27
@@ -102,7 +137,8 @@ mod test {
 
     #[test]
     fn test_part1_full() {
-        assert_eq!(part1(FULL_INPUT), 20665830408335);
+        let input = aoc_utils::full_input!(7);
+        assert_eq!(part1(&input), 20665830408335);
     }
 
     #[test]
@@ -112,6 +148,15 @@ mod test {
 
     #[test]
     fn test_part2_full() {
-        assert_eq!(part2(FULL_INPUT), 354060705047464);
+        let input = aoc_utils::full_input!(7);
+        assert_eq!(part2(&input), 354060705047464);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = try_part1("190: 10 19\n3267 81 40 27\n").unwrap_err();
+        assert_eq!((err.line, err.column), (Some(2), Some(5)));
+        let err = try_part2("190: 10 19\n83: 17 5x\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 9: unexpected text after operands");
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,39 @@
 use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
+use aoc_utils::{AocError, Grid, Solution, Variant};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+//
+// The map is a rectangle of open squares and antennas.  An antenna's
+// frequency is a letter or digit.
+//
+fn check_map(input: &str) -> Result<(), AocError> {
+    Grid::parse_checked(input, |ch| ch == '.' || ch.is_ascii_alphanumeric())?;
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part2(input))
+}
 
 struct Position(i32, i32);
 
@@ -185,3 +219,10 @@ T.........
         assert_eq!(part2(input), 34);
     }
 }
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("..a\n.#.\n").unwrap_err().to_string(), "line 2, column 2: unexpected '#'");
+    assert_eq!(try_part2("..a\n..\n").unwrap_err().to_string(), "line 2: expected 3 columns, found 2");
+    assert_eq!(try_part1("a..\n..a\n"), Ok(0));
+}
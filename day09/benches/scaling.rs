@@ -0,0 +1,32 @@
+//
+// Disk maps of increasing length, to see how part2 and part2_heaps
+// cope with more files to move.
+//
+use aoc_utils::bench::generated;
+use divan::Bencher;
+use day09::{ part1, part2, part2_heaps };
+
+fn main() {
+    divan::main();
+}
+
+// Digits in the disk map (the real one has 19999)
+const SIZES: &[usize] = &[1249, 2499, 4999, 9999, 19999, 39999];
+
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, size: usize) {
+    let input = generated(9, size);
+    bencher.bench(|| part1(&input));
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, size: usize) {
+    let input = generated(9, size);
+    bencher.bench(|| part2(&input));
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_part2_heaps(bencher: Bencher, size: usize) {
+    let input = generated(9, size);
+    bencher.bench(|| part2_heaps(&input));
+}
@@ -1,4 +1,44 @@
 use std::iter::repeat_n;
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    type Answer1 = u64;
+    type Answer2 = u64;
+    const PART1: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<u64>] = &[
+        Variant::new("heaps", part2_heaps),
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+//
+// The disk map is a single line of digits.
+//
+fn check_disk_map(input: &str) -> Result<(), AocError> {
+    let input = input.trim_end();
+    if input.is_empty() {
+        return Err(AocError::new("empty disk map"));
+    }
+    match input.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+        Some((offset, ch)) => Err(AocError::at_offset(input, offset, format!("expected a digit, found {ch:?}"))),
+        None => Ok(()),
+    }
+}
+
+pub fn try_part1(input: &str) -> Result<u64, AocError> {
+    check_disk_map(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<u64, AocError> {
+    check_disk_map(input)?;
+    Ok(part2(input))
+}
 
 //
 // How do we want to represent which blocks are occupied by which file ID?
@@ -203,9 +243,6 @@ pub fn part2_heaps(input: &str) -> u64 {
     .sum()
 }
 
-#[cfg(test)]
-const FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1() {
     let input = "2333133121414131402";
@@ -214,7 +251,8 @@ fn test_part1() {
 
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 6367087064415);
+    let input = aoc_utils::full_input!(9);
+    assert_eq!(part1(&input), 6367087064415);
 }
 
 #[test]
@@ -225,7 +263,8 @@ fn test_part2() {
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 6390781891880);
+    let input = aoc_utils::full_input!(9);
+    assert_eq!(part2(&input), 6390781891880);
 }
 
 #[test]
@@ -236,5 +275,25 @@ fn test_part2_heaps() {
 
 #[test]
 fn test_part2_heaps_full() {
-    assert_eq!(part2_heaps(FULL_INPUT), 6390781891880);
+    let input = aoc_utils::full_input!(9);
+    assert_eq!(part2_heaps(&input), 6390781891880);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("\n"), Err(AocError::new("empty disk map")));
+    assert_eq!(try_part2("12345\n6789\n").unwrap_err().to_string(), "line 1, column 6: expected a digit, found '\\n'");
+    assert_eq!(try_part1("12345\n"), Ok(part1("12345\n")));
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "2333133121414131402";
+    aoc_utils::assert_variants_agree::<Day09>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(9);
+    aoc_utils::assert_variants_agree::<Day09>(&input);
 }
@@ -1,45 +1,57 @@
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+//
+// The map is a rectangle of heights (digits).  Examples use '.' for
+// squares that aren't part of any trail.
+//
+fn check_map(input: &str) -> Result<(), AocError> {
+    aoc_utils::Grid::parse_checked(input, |ch| ch == '.' || ch.is_ascii_digit())?;
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part2(input))
+}
+
 mod part1 {
+    use aoc_utils::Grid;
     use pathfinding::prelude::bfs_reach;
-    use std::collections::HashMap;
-    type Row = isize;
-    type Col = isize;
-    type Grid = HashMap<(Row, Col), u8>;
     pub fn part1(input: &str) -> usize {
         // Parse the input
-        let mut grid: Grid = Grid::new();
-        let mut zeroes = Vec::new();
-        for (row, line) in input.lines().enumerate() {
-            let row = row as isize;
-            for (col, byte) in line.bytes().enumerate() {
-                let col = col as isize;
-                grid.insert((row, col), byte);
-                if byte == b'0' { zeroes.push((row, col)) }
-            }
-        }
+        let grid: Grid<u8> = input.parse().expect("rectangular grid");
+        let grid = &grid;
 
         // For each trailhead (b'0'), see how many unique trail ends (b'9')
         // are reachable.
-        zeroes.into_iter().map(|(row, col)|
+        grid.find_all(&b'0').map(|trailhead|
             bfs_reach(
-                (row, col, &b'0'),
-                |&(r, c, h)| {
-                    [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)]
-                        .into_iter()
-                        .filter_map(|(rr, cc)| {
-                            let h = *h;
-                            if let Some(neighbor_height) = grid.get(&(rr,cc)) {
-                                if *neighbor_height == h + 1 {
-                                    Some((rr, cc, neighbor_height))
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            }
-                        })
+                trailhead,
+                |&pos| {
+                    let height = grid[pos];
+                    grid.neighbors4(pos).filter(move |&neighbor| grid[neighbor] == height + 1)
                 }
             )
-            .filter(|(_r, _c, h)| **h == b'9')
+            .filter(|&pos| grid[pos] == b'9')
             .count()
         ).sum()
     }
@@ -47,39 +59,24 @@ mod part1 {
 pub use part1::part1;
 
 pub fn part2(input: &str) -> usize {
+    use aoc_utils::Grid;
     use pathfinding::prelude::count_paths;
-    use std::collections::HashMap;
-    type Row = isize;
-    type Col = isize;
-    type Grid = HashMap<(Row, Col), u8>;
 
     // Parse the input
-    let mut grid: Grid = Grid::new();
-    let mut zeroes = Vec::new();
-    for (row, line) in input.lines().enumerate() {
-        let row = row as isize;
-        for (col, byte) in line.bytes().enumerate() {
-            let col = col as isize;
-            grid.insert((row, col), byte);
-            if byte == b'0' {
-                zeroes.push((row, col))
-            }
-        }
-    }
+    let grid: Grid<u8> = input.parse().expect("rectangular grid");
+    let grid = &grid;
 
     let mut result = 0;
     // For each trailhead (b'0'), see how many unique paths to a trail
     // end (b'9').
-    for (row, col) in zeroes {
+    for trailhead in grid.find_all(&b'0') {
         result += count_paths(
-            (row, col),
-            |&(r, c)| {
-                let height = grid.get(&(r, c)).unwrap();
-                [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)]
-                    .into_iter()
-                    .filter(|&(rr, cc)| grid.get(&(rr, cc)) == Some(&(*height + 1)))
+            trailhead,
+            |&pos| {
+                let height = grid[pos];
+                grid.neighbors4(pos).filter(move |&neighbor| grid[neighbor] == height + 1)
             },
-            |loc| grid.get(loc) == Some(&b'9'),
+            |&pos| grid[pos] == b'9',
         );
     }
     result
@@ -195,15 +192,20 @@ fn test_part2d() {
     assert_eq!(part2(input), 81);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 798);
+    let input = aoc_utils::full_input!(10);
+    assert_eq!(part1(&input), 798);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 1816);
+    let input = aoc_utils::full_input!(10);
+    assert_eq!(part2(&input), 1816);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("0123\n1234\n8765\n9x76\n").unwrap_err().to_string(), "line 4, column 2: unexpected 'x'");
+    assert_eq!(try_part2("0123\n123\n").unwrap_err().to_string(), "line 2: expected 4 columns, found 3");
 }
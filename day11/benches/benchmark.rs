@@ -1,17 +1,24 @@
+use std::sync::LazyLock;
+
+use aoc_utils::{input_path, load_input};
 use day11::{ part1, part2 };
 
 fn main() {
+    if let Err(err) = input_path(11) {
+        eprintln!("Skipping benchmarks: {err}");
+        return;
+    }
     divan::main();
 }
 
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(|| load_input(11).expect("input"));
 
 #[divan::bench]
 fn bench_part1() {
-    part1(INPUT);
+    part1(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2() {
-    part2(INPUT);
+    part2(&INPUT);
 }
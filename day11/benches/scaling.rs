@@ -0,0 +1,28 @@
+//
+// How the running time grows with the number of blinks (part 1 is 25,
+// and part 2 is 75), and with the number of stones.
+//
+use aoc_utils::bench::generated;
+use divan::Bencher;
+use day11::blink;
+
+fn main() {
+    divan::main();
+}
+
+// Much past 90 blinks, the number of stones overflows
+const BLINKS: &[usize] = &[10, 25, 50, 75, 90];
+// Stones (the real input has 8)
+const STONES: &[usize] = &[1, 8, 64, 512];
+
+#[divan::bench(args = BLINKS)]
+fn bench_blinks(bencher: Bencher, blinks: usize) {
+    let input = generated(11, 8);
+    bencher.bench(|| blink(&input, blinks));
+}
+
+#[divan::bench(args = STONES)]
+fn bench_stones(bencher: Bencher, stones: usize) {
+    let input = generated(11, stones);
+    bencher.bench(|| blink(&input, 75));
+}
@@ -1,6 +1,52 @@
 use std::collections::HashMap;
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+//
+// The stones are a single line of numbers separated by spaces.
+//
+fn check_stones(input: &str) -> Result<(), AocError> {
+    let mut stones = input.trim_end().split_ascii_whitespace().peekable();
+    if stones.peek().is_none() {
+        return Err(AocError::new("no stones"));
+    }
+    for stone in stones {
+        stone.parse::<u64>()?;
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_stones(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    check_stones(input)?;
+    Ok(part2(input))
+}
 
 pub fn part1(input: &str) -> usize {
+    blink(input, 25)
+}
+
+//
+// How many stones there are after blinking the given number of times.
+//
+pub fn blink(input: &str, blinks: usize) -> usize {
     let mut cache: HashMap<(u64, usize), usize> = HashMap::new();
     let seeds = input
         .trim_end()
@@ -8,7 +54,7 @@ pub fn part1(input: &str) -> usize {
         .map(|s| s.parse().unwrap());
 
     seeds
-        .map(|seed| expanded_length(seed, 25, &mut cache))
+        .map(|seed| expanded_length(seed, blinks, &mut cache))
         .sum()
 }
 
@@ -53,15 +99,7 @@ fn expanded_length(
 }
 
 pub fn part2(input: &str) -> usize {
-    let mut cache: HashMap<(u64, usize), usize> = HashMap::new();
-    let seeds = input
-        .trim_end()
-        .split_ascii_whitespace()
-        .map(|s| s.parse().unwrap());
-
-    seeds
-        .map(|seed| expanded_length(seed, 75, &mut cache))
-        .sum()
+    blink(input, 75)
 }
 
 #[test]
@@ -69,15 +107,26 @@ fn test_part1() {
     assert_eq!(part1("125 17"), 55312);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
+#[test]
+fn test_blink() {
+    assert_eq!(blink("125 17", 0), 2);
+    assert_eq!(blink("125 17", 6), 22);
+}
 
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 209412);
+    let input = aoc_utils::full_input!(11);
+    assert_eq!(part1(&input), 209412);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 248967696501656);
+    let input = aoc_utils::full_input!(11);
+    assert_eq!(part2(&input), 248967696501656);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1(" \n"), Err(AocError::new("no stones")));
+    assert_eq!(try_part2("125 17x\n").unwrap_err().to_string(), "invalid number: invalid digit found in string");
 }
@@ -1,22 +1,29 @@
+use std::sync::LazyLock;
+
+use aoc_utils::{input_path, load_input};
 use day12::{ part1, part2, part2_ndarray };
 
 fn main() {
+    if let Err(err) = input_path(12) {
+        eprintln!("Skipping benchmarks: {err}");
+        return;
+    }
     divan::main();
 }
 
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(|| load_input(12).expect("input"));
 
 #[divan::bench]
 fn bench_part1() {
-    part1(INPUT);
+    part1(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2() {
-    part2(INPUT);
+    part2(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2_ndarray() {
-    part2_ndarray(INPUT);
+    part2_ndarray(&INPUT);
 }
@@ -1,5 +1,40 @@
 use rustc_hash::{FxHashMap, FxHashSet};
 use aoc_utils::HashPop;
+use aoc_utils::{AocError, Grid, Solution, Variant};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part2),
+        Variant::new("ndarray", part2_ndarray),
+    ];
+}
+
+//
+// The map is a rectangle of garden plots, each labeled with the letter
+// of the plant growing there.
+//
+fn check_map(input: &str) -> Result<(), AocError> {
+    Grid::parse_checked(input, |ch| ch.is_ascii_alphabetic())?;
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    check_map(input)?;
+    Ok(part2(input))
+}
 
 //
 // Find regions of adjacent cells with the same letter.  Determine the
@@ -377,20 +412,49 @@ MMMISSJEEE
     assert_eq!(part2(input), 1206);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 1375476);
+    let input = aoc_utils::full_input!(12);
+    assert_eq!(part1(&input), 1375476);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 821372);
+    let input = aoc_utils::full_input!(12);
+    assert_eq!(part2(&input), 821372);
 }
 
 #[test]
 fn test_part2_ndarray_full() {
-    assert_eq!(part2_ndarray(FULL_INPUT), 821372);
+    let input = aoc_utils::full_input!(12);
+    assert_eq!(part2_ndarray(&input), 821372);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("AAAA\nBB.C\n").unwrap_err().to_string(), "line 2, column 3: unexpected '.'");
+    assert_eq!(try_part2("AAAA\nBBC\n").unwrap_err().to_string(), "line 2: expected 4 columns, found 3");
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+    aoc_utils::assert_variants_agree::<Day12>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(12);
+    aoc_utils::assert_variants_agree::<Day12>(&input);
 }
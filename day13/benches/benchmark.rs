@@ -1,23 +1,30 @@
+use std::sync::LazyLock;
+
+use aoc_utils::{input_path, load_input};
 use day13::{ part1, part2, parse_machines };
 
 fn main() {
+    if let Err(err) = input_path(13) {
+        eprintln!("Skipping benchmarks: {err}");
+        return;
+    }
     divan::main();
 }
 
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(|| load_input(13).expect("input"));
 
 #[divan::bench]
 fn bench_part1() {
-    part1(INPUT);
+    part1(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2() {
-    part2(INPUT);
+    part2(&INPUT);
 }
 
 #[divan::bench]
 fn bench_parsing() {
-    let (_, machines) = parse_machines(INPUT).expect("well formed input");
+    let (_, machines) = parse_machines(&INPUT).expect("well formed input");
     assert!(machines.len() > 1);
 }
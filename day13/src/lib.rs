@@ -1,4 +1,19 @@
 use nom::{bytes::complete::tag, character::complete::{multispace0, i64}, multi::many1, IResult, Parser};
+use aoc_utils::{AocError, NomResultExt, Solution, Variant};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Answer1 = i64;
+    type Answer2 = i64;
+    const PART1: &'static [Variant<i64>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<i64>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
 
 //
 // Part 1
@@ -65,9 +80,29 @@ pub fn parse_machines(input: &str) -> IResult<&str, Vec<Machine>> {
     many1(parse_machine).parse(input)
 }
 
+//
+// Parse the whole input.  The solutions divide by the determinant of the
+// buttons' movements, so a machine whose buttons move in the same
+// direction is an error.
+//
+fn parse_all_machines(input: &str) -> Result<Vec<Machine>, AocError> {
+    let (rest, machines) = parse_machines(input).located_in(input)?;
+    if !rest.trim().is_empty() {
+        return Err(AocError::at(input, rest, "expected a machine"));
+    }
+    if let Some(i) = machines.iter().position(|m| m.b.y * m.a.x == m.b.x * m.a.y) {
+        return Err(AocError::new(format!("machine {}: buttons A and B move in the same direction", i + 1)));
+    }
+    Ok(machines)
+}
+
 pub fn part1(input: &str) -> i64 {
-    let (_, machines) = parse_machines(input).expect("well formed input");
-    machines.iter().filter_map(|m| {
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str) -> Result<i64, AocError> {
+    let machines = parse_all_machines(input)?;
+    Ok(machines.iter().filter_map(|m| {
         let denominator = m.b.y * m.a.x - m.b.x * m.a.y;
         let num_m = m.b.y * m.prize.x - m.b.x * m.prize.y;
         let num_n = m.a.x * m.prize.y - m.a.y * m.prize.x;
@@ -80,12 +115,16 @@ pub fn part1(input: &str) -> i64 {
             }
         }
         None
-    }).sum()
+    }).sum())
 }
 
 pub fn part2(input: &str) -> i64 {
-    let (_, machines) = parse_machines(input).expect("well formed input");
-    machines.iter().filter_map(|m| {
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part2(input: &str) -> Result<i64, AocError> {
+    let machines = parse_all_machines(input)?;
+    Ok(machines.iter().filter_map(|m| {
         let px = m.prize.x + 10000000000000;
         let py = m.prize.y + 10000000000000;
         let denominator = m.b.y * m.a.x - m.b.x * m.a.y;
@@ -100,7 +139,7 @@ pub fn part2(input: &str) -> i64 {
             }
         }
         None
-    }).sum()
+    }).sum())
 }
 
 #[test]
@@ -147,15 +186,35 @@ Prize: X=18641, Y=10279
     assert_eq!(part2(input), 875318608908);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 26810);
+    let input = aoc_utils::full_input!(13);
+    assert_eq!(part1(&input), 26810);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 108713182988244);
+    let input = aoc_utils::full_input!(13);
+    assert_eq!(part2(&input), 108713182988244);
+}
+
+#[test]
+fn test_invalid_input() {
+    let input = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67 Y+21
+Prize: X=12748, Y=12176
+";
+    let err = try_part1(input).unwrap_err();
+    assert_eq!((err.line, err.column), (Some(5), Some(1)));
+    let input = "\
+Button A: X+2, Y+4
+Button B: X+1, Y+2
+Prize: X=8400, Y=5400
+";
+    assert_eq!(try_part2(input), Err(AocError::new("machine 1: buttons A and B move in the same direction")));
 }
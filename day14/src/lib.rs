@@ -1,6 +1,21 @@
 use std::collections::HashSet;
 
 use nom::{bytes::complete::tag, character::complete::{self,line_ending}, multi::separated_list1, sequence::{preceded, separated_pair}, IResult, Parser};
+use aoc_utils::{AocError, NomResultExt, Solution, Variant};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Answer1 = usize;
+    type Answer2 = u32;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<u32>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
 
 struct Coord {
     x: i32,
@@ -17,8 +32,21 @@ impl Robot {
         self.position.y = (self.position.y + self.velocity.y).rem_euclid(103);
     }
 }
+fn parse_robots(input: &str) -> Result<Vec<Robot>, AocError> {
+    let (rest, robots) = separated_list1(line_ending, parse_line).parse(input).located_in(input)?;
+    if !rest.trim_end().is_empty() {
+        let rest = rest.trim_start_matches(['\r', '\n']);
+        return Err(AocError::at(input, rest, "expected a robot like p=0,4 v=3,-3"));
+    }
+    Ok(robots)
+}
+
 pub fn part1_with_size(input: &str, width: i32, height: i32) -> usize {
-    let (_rest, robots) = separated_list1(line_ending, parse_line).parse(input).expect("valid input");
+    try_part1_with_size(input, width, height).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1_with_size(input: &str, width: i32, height: i32) -> Result<usize, AocError> {
+    let robots = parse_robots(input)?;
     let mut upper_left = 0;
     let mut upper_right = 0;
     let mut lower_left = 0;
@@ -38,15 +66,19 @@ pub fn part1_with_size(input: &str, width: i32, height: i32) -> usize {
         }
     }
 
-    upper_left * upper_right * lower_left * lower_right
+    Ok(upper_left * upper_right * lower_left * lower_right)
 }
 
 pub fn part1(input: &str) -> usize {
     part1_with_size(input, 101, 103)
 }
 
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    try_part1_with_size(input, 101, 103)
+}
+
 pub fn part2_helper(input: &str) {
-    let (_rest, mut robots) = separated_list1(line_ending, parse_line).parse(input).expect("valid input");
+    let mut robots = parse_robots(input).unwrap_or_else(|err| panic!("{err}"));
     for i in 1..10000 {
         let mut grid = [[b' ';101]; 103];
         robots.iter_mut().for_each(Robot::update);
@@ -64,6 +96,15 @@ pub fn part2_helper(input: &str) {
     }
 }
 
+//
+// Part 2 doesn't actually look at the robots (see below), but they
+// should at least be valid robots.
+//
+pub fn try_part2(input: &str) -> Result<u32, AocError> {
+    parse_robots(input)?;
+    Ok(part2())
+}
+
 pub fn part2() -> u32 {
     // I've noticed that the output of part2_helper() has notable horizontal
     // content for iterations of the form 63 + M * 103, and vertical for
@@ -90,9 +131,6 @@ fn parse_line(input: &str) -> IResult<&str, Robot> {
     .map(|(rest, (position, velocity))| (rest, Robot{position, velocity}))
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1() {
     let input = "\
@@ -114,5 +152,13 @@ p=9,5 v=-3,-3
 
 #[test]
 fn part1_full() {
-    assert_eq!(part1(FULL_INPUT), 222901875);
+    let input = aoc_utils::full_input!(14);
+    assert_eq!(part1(&input), 222901875);
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = try_part1("p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 -1,2\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 3, column 1: expected a robot like p=0,4 v=3,-3");
+    assert!(try_part2("p=0,4 v=3,x\n").is_err());
 }
@@ -1,9 +1,10 @@
+use aoc_utils::load_input_or_exit;
 use day14::{part1, part2};
 
 fn main() {
-    let input = include_str!("../input.txt");
+    let input = load_input_or_exit(14);
 
-    let result1 = part1(input);
+    let result1 = part1(&input);
     println!("Part 1: {result1}");
 
     let result2 = part2();
@@ -1,4 +1,20 @@
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use aoc_utils::{AocError, Grid, Solution, Variant};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Answer1 = i32;
+    type Answer2 = i32;
+    const PART1: &'static [Variant<i32>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<i32>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
 // use std::collections::{HashMap, HashSet};
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
@@ -42,6 +58,44 @@ fn print_grid(grid: &HashMap<Coord, u8>) {
     }
 }
 
+//
+// The warehouse must be surrounded by walls (or the robot could push
+// boxes forever), with one robot.  The moves are arrows, possibly split
+// across several lines.
+//
+fn check_input(input: &str) -> Result<(), AocError> {
+    let (warehouse, moves) = input.split_once("\n\n")
+        .ok_or_else(|| AocError::new("expected a blank line between the warehouse and the moves"))?;
+    let grid = Grid::parse_checked(warehouse, |ch| ".#O@".contains(ch))?;
+    let last_row = grid.rows() - 1;
+    let last_col = grid.cols() - 1;
+    let on_edge = |&(row, col): &(usize, usize)| row == 0 || col == 0 || row == last_row || col == last_col;
+    if let Some(pos) = grid.positions().filter(on_edge).find(|&pos| grid[pos] != b'#') {
+        return Err(AocError::at_cell(pos, "the warehouse must be surrounded by walls"));
+    }
+    match grid.find_all(&b'@').count() {
+        0 => return Err(AocError::new("did not find robot's initial position")),
+        1 => {}
+        n => return Err(AocError::new(format!("expected one robot, found {n}"))),
+    }
+
+    let moves_start = warehouse.len() + 2;
+    if let Some((offset, ch)) = moves.char_indices().find(|&(_, ch)| !"<>^v\n".contains(ch)) {
+        return Err(AocError::at_offset(input, moves_start + offset, format!("unknown move {ch:?}")));
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<i32, AocError> {
+    check_input(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<i32, AocError> {
+    check_input(input)?;
+    Ok(part2(input))
+}
+
 pub fn part1(input: &str) -> i32 {
     let (warehouse, moves) = input.split_once("\n\n").expect("missing empyty line?");
 
@@ -261,9 +315,6 @@ pub fn part2(input: &str) -> i32 {
     boxes.into_iter().map(|Coord(row, col)| 100 * row + col).sum()
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_tiny() {
     let input = "\
@@ -311,7 +362,8 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
 
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 1552879);
+    let input = aoc_utils::full_input!(15);
+    assert_eq!(part1(&input), 1552879);
 }
 
 #[test]
@@ -360,5 +412,18 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 1561175);
+    let input = aoc_utils::full_input!(15);
+    assert_eq!(part2(&input), 1561175);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("#####\n#.@O#\n#####\n"),
+        Err(AocError::new("expected a blank line between the warehouse and the moves")));
+    assert_eq!(try_part1("#####\n#.@O.\n#####\n\n<>\n").unwrap_err().to_string(),
+        "line 2, column 5: the warehouse must be surrounded by walls");
+    assert_eq!(try_part2("#####\n#.@O#\n#####\n\n<>\n^x\n").unwrap_err().to_string(),
+        "line 6, column 2: unknown move 'x'");
+    assert_eq!(try_part2("#####\n#..O#\n#####\n\n<>\n"),
+        Err(AocError::new("did not find robot's initial position")));
 }
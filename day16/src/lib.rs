@@ -1,34 +1,38 @@
 use rustc_hash::{FxHashMap, FxHashSet};
 use pathfinding::prelude::{astar, astar_bag};
+use aoc_utils::{AocError, Direction, Grid, Solution, Variant};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    East,
-    North,
-    West,
-    South    
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part2),
+    ];
 }
-impl Direction {
-    fn clockwise(&self) -> Self {
-        match self {
-            Self::East => Self::South,
-            Self::North => Self::East,
-            Self::West => Self::North,
-            Self::South => Self::West
-        }
-    }
 
-    fn counterclockwise(&self) -> Self {
-        match self {
-            Self::East => Self::North,
-            Self::North => Self::West,
-            Self::West => Self::South,
-            Self::South => Self::East
+type Location = (usize, usize);
+type Maze = FxHashMap<Location, u8>;
+
+//
+// Parse the maze, returning the map (with the start and end marked as
+// open), and the start and end locations.
+//
+fn parse_maze(input: &str) -> Result<(Maze, Location, Location), AocError> {
+    let map = Grid::parse_checked(input, |ch| ".#SE".contains(ch))?;
+    let last_row = map.rows() - 1;
+    let last_col = map.cols() - 1;
+    for (row, col) in map.positions() {
+        if (row == 0 || col == 0 || row == last_row || col == last_col) && map[(row, col)] != b'#' {
+            return Err(AocError::at_cell((row, col), "the maze must be surrounded by walls"));
         }
     }
-}
 
-pub fn part1(input: &str) -> usize {
     let mut grid = FxHashMap::default();
     let mut start = None;
     let mut end = None;
@@ -49,8 +53,18 @@ pub fn part1(input: &str) -> usize {
             }
         }
     }
-    let start = (start.expect("no start?"), Direction::East);
-    let end = end.expect("no end?");
+    let start = start.ok_or_else(|| AocError::new("no start?"))?;
+    let end = end.ok_or_else(|| AocError::new("no end?"))?;
+    Ok((grid, start, end))
+}
+
+pub fn part1(input: &str) -> usize {
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    let (grid, start, end) = parse_maze(input)?;
+    let start = (start, Direction::East);
 
     // Note the ": &_" bit is to work around a compiler error.
     // See https://github.com/rust-lang/rust/issues/70263
@@ -61,8 +75,8 @@ pub fn part1(input: &str) -> usize {
     let successors = |&((row, col), direction) : &((usize, usize), Direction)| {
         // produce something that can turn into an iterator of (Node, Cost)
         let mut result = vec![
-            (((row, col), direction.clockwise()), 1000),
-            (((row, col), direction.counterclockwise()), 1000)
+            (((row, col), direction.turn_right()), 1000),
+            (((row, col), direction.turn_left()), 1000)
         ];
 
         let forward = match direction {
@@ -77,33 +91,18 @@ pub fn part1(input: &str) -> usize {
 
         result
     };
-    let (_path, cost) = astar(&start, successors, heuristic, success).expect("no solution found");
-    cost
+    let (_path, cost) = astar(&start, successors, heuristic, success)
+        .ok_or_else(|| AocError::new("no path from start to end"))?;
+    Ok(cost)
 }
 
 pub fn part2(input: &str) -> usize {
-    let mut grid = FxHashMap::default();
-    let mut start = None;
-    let mut end = None;
-    for (row, line) in input.lines().enumerate() {
-        for (col, ch) in line.bytes().enumerate() {
-            match ch {
-                b'S' => {
-                    start = Some((row, col));
-                    grid.insert((row, col), b'.');
-                }
-                b'E' => {
-                    end = Some((row, col));
-                    grid.insert((row, col), b'.');
-                }
-                _ => {
-                    grid.insert((row, col), ch);
-                }
-            }
-        }
-    }
-    let start = (start.expect("no start?"), Direction::East);
-    let end = end.expect("no end?");
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    let (grid, start, end) = parse_maze(input)?;
+    let start = (start, Direction::East);
 
     // Note the ": &_" bit is to work around a compiler error.
     // See https://github.com/rust-lang/rust/issues/70263
@@ -114,8 +113,8 @@ pub fn part2(input: &str) -> usize {
     let successors = |&((row, col), direction) : &((usize, usize), Direction)| {
         // produce something that can turn into an iterator of (Node, Cost)
         let mut result = vec![
-            (((row, col), direction.clockwise()), 1000),
-            (((row, col), direction.counterclockwise()), 1000)
+            (((row, col), direction.turn_right()), 1000),
+            (((row, col), direction.turn_left()), 1000)
         ];
 
         let forward = match direction {
@@ -130,7 +129,8 @@ pub fn part2(input: &str) -> usize {
 
         result
     };
-    let (paths, _cost) = astar_bag(&start, successors, heuristic, success).expect("no solution found");
+    let (paths, _cost) = astar_bag(&start, successors, heuristic, success)
+        .ok_or_else(|| AocError::new("no path from start to end"))?;
     
     let mut locations = FxHashSet::default();
     for path in paths {
@@ -139,7 +139,7 @@ pub fn part2(input: &str) -> usize {
         }
     }
 
-    locations.len()
+    Ok(locations.len())
 }
 
 #[test]
@@ -234,15 +234,23 @@ fn test_part2_example_2() {
     assert_eq!(part2(input), 64);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 66404);
+    let input = aoc_utils::full_input!(16);
+    assert_eq!(part1(&input), 66404);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 433);
+    let input = aoc_utils::full_input!(16);
+    assert_eq!(part2(&input), 433);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("#####\n#S..#\n#####\n"), Err(AocError::new("no end?")));
+    assert_eq!(try_part2("#####\n#..E#\n#####\n"), Err(AocError::new("no start?")));
+    assert_eq!(try_part1("#####\n#S#E#\n#####\n"), Err(AocError::new("no path from start to end")));
+    assert_eq!(try_part2("#####\n#S.E.\n#####\n").unwrap_err().to_string(), "line 2, column 5: the maze must be surrounded by walls");
+    assert_eq!(try_part1("#####\n#S.E#\n#####\n"), Ok(2));
 }
@@ -1,6 +1,21 @@
 use itertools::Itertools;
 use nom::{bytes::tag, character::complete::{self, newline}, combinator::all_consuming, multi::separated_list1, sequence::{delimited, pair}, IResult, Parser};
 use num::pow;
+use aoc_utils::{AocError, NomResultExt, Solution, Variant};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Answer1 = String;
+    type Answer2 = u64;
+    const PART1: &'static [Variant<String>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
 
 struct Computer {
     reg_a: u64,
@@ -93,10 +108,40 @@ fn parse_input(input: &str) -> IResult<&str, Computer> {
     Ok((remaining, Computer::new(reg_a, reg_b, reg_c, program)))
 }
 
+//
+// Parse the input, and make sure the program won't trip over an invalid
+// opcode or argument when run.
+//
+fn parse_computer(input: &str) -> Result<Computer, AocError> {
+    let (_, computer) = parse_input(input).located_in(input)?;
+    let program = &computer.program;
+    if program.len() % 2 != 0 {
+        return Err(AocError::new("the program must be pairs of opcode and argument"));
+    }
+    for (i, pair) in program.chunks(2).enumerate() {
+        let (opcode, argument) = (pair[0], pair[1]);
+        let address = 2 * i;
+        if opcode > 7 {
+            return Err(AocError::new(format!("invalid opcode {opcode} at address {address}")));
+        }
+        if argument > 7 || (argument == 7 && matches!(opcode, 0 | 2 | 5 | 6 | 7)) {
+            return Err(AocError::new(format!("invalid argument {argument} at address {}", address + 1)));
+        }
+        if opcode == 3 && argument % 2 != 0 {
+            return Err(AocError::new(format!("jump to odd address {argument} at address {address}")));
+        }
+    }
+    Ok(computer)
+}
+
 pub fn part1(input: &str) -> String {
-    let mut computer = parse_input(input).expect("invalid input").1;
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str) -> Result<String, AocError> {
+    let mut computer = parse_computer(input)?;
     computer.run_program();
-    computer.get_output()
+    Ok(computer.get_output())
 }
 
 //
@@ -119,7 +164,11 @@ pub fn part1(input: &str) -> String {
 // significant digit, and so on.
 //
 pub fn part2(input: &str) -> u64 {
-    let mut computer = parse_input(input).expect("invalid input").1;
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part2(input: &str) -> Result<u64, AocError> {
+    let mut computer = parse_computer(input)?;
     let mut place_value = pow(8, computer.program.len() - 1);
     let mut digits_to_find = computer.program.len();
     let mut a = place_value;
@@ -147,7 +196,7 @@ pub fn part2(input: &str) -> u64 {
         computer.run_program();
     }
 
-    a
+    Ok(a)
 }
 
 #[test]
@@ -174,15 +223,26 @@ Program: 0,3,5,4,3,0
     assert_eq!(part2(input), 117440);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), "2,3,4,7,5,7,3,0,7");
+    let input = aoc_utils::full_input!(17);
+    assert_eq!(part1(&input), "2,3,4,7,5,7,3,0,7");
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 190384609508367);
+    let input = aoc_utils::full_input!(17);
+    assert_eq!(part2(&input), 190384609508367);
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = try_part1("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3\n").unwrap_err();
+    assert_eq!(err, AocError::new("the program must be pairs of opcode and argument"));
+    let err = try_part1("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,7,3,0\n").unwrap_err();
+    assert_eq!(err, AocError::new("invalid argument 7 at address 3"));
+    let err = try_part2("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,8,4,3,0\n").unwrap_err();
+    assert_eq!(err, AocError::new("invalid opcode 8 at address 2"));
+    let err = try_part2("Register A: 729\nRegister B: 0\nRegister: 0\n\nProgram: 0,1,5,4,3,0\n").unwrap_err();
+    assert_eq!((err.line, err.column), (Some(3), Some(1)));
 }
@@ -1,17 +1,39 @@
 use pathfinding::prelude::astar;
 use rustc_hash::FxHashSet;
+use aoc_utils::{AocError, LineContext, Solution, Variant};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Answer1 = u32;
+    type Answer2 = String;
+    const PART1: &'static [Variant<u32>] = &[
+        Variant::fallible("default", |input| try_part1(input, 1024, 70)),
+    ];
+    const PART2: &'static [Variant<String>] = &[
+        Variant::fallible("default", |input| {
+            let (x, y) = try_part2(input, 1024, 70)?;
+            Ok(format!("{x},{y}"))
+        }),
+    ];
+}
 
 struct Point {
     x: i32,
     y: i32
 }
 
-fn parse_input(input: &str) -> Vec<Point> {
-    input.lines().map(|line| {
-        let (x,y) = line.split_once(",").expect("a point");
-        let x = x.parse::<i32>().expect("valid number");
-        let y = y.parse::<i32>().expect("valid number");
-        Point{x,y}
+fn parse_input(input: &str, max_dimension: i32) -> Result<Vec<Point>, AocError> {
+    input.lines().enumerate().map(|(i, line)| {
+        let (x,y) = line.split_once(",")
+            .ok_or_else(|| AocError::at_line(i + 1, format!("expected a point like 5,4, found {line:?}")))?;
+        let x = x.parse::<i32>().on_line(i + 1)?;
+        let y = y.parse::<i32>().on_line(i + 1)?;
+        if !(0..=max_dimension).contains(&x) || !(0..=max_dimension).contains(&y) {
+            return Err(AocError::at_line(i + 1, format!("{x},{y} is outside the memory space")));
+        }
+        Ok(Point{x,y})
     }).collect()
 }
 
@@ -53,8 +75,13 @@ fn path_length(points: &[Point], num_points: usize, max_dimension: i32) -> Optio
 }
 
 pub fn part1(input: &str, num_points: usize, max_dimension: i32) -> u32 {
-    let points = parse_input(input);
-    path_length(&points, num_points, max_dimension).expect("a solution")
+    try_part1(input, num_points, max_dimension).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str, num_points: usize, max_dimension: i32) -> Result<u32, AocError> {
+    let points = parse_input(input, max_dimension)?;
+    path_length(&points, num_points, max_dimension)
+        .ok_or_else(|| AocError::new(format!("no path after {num_points} bytes have fallen")))
 }
 
 //
@@ -65,7 +92,17 @@ pub fn part1(input: &str, num_points: usize, max_dimension: i32) -> u32 {
 // to find the critical point.
 //
 pub fn part2(input: &str, num_points: usize, max_dimension: i32) -> (i32, i32) {
-    let points = parse_input(input);
+    try_part2(input, num_points, max_dimension).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part2(input: &str, num_points: usize, max_dimension: i32) -> Result<(i32, i32), AocError> {
+    let points = parse_input(input, max_dimension)?;
+    if path_length(&points, num_points, max_dimension).is_none() {
+        return Err(AocError::new(format!("no path after {num_points} bytes have fallen")));
+    }
+    if path_length(&points, points.len(), max_dimension).is_some() {
+        return Err(AocError::new("the path is never blocked"));
+    }
 
     let mut solvable = num_points;        // From part 1, we know this many points is OK
     let mut unsolvable = input.lines().count();
@@ -80,7 +117,7 @@ pub fn part2(input: &str, num_points: usize, max_dimension: i32) -> (i32, i32) {
     }
     
     let Point{x,y} = points[unsolvable-1];
-    (x,y)
+    Ok((x,y))
 }
 
 #[test]
@@ -147,15 +184,22 @@ fn test_part2() {
     assert_eq!(part2(input, 12, 6), (6,1));
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT, 1024, 70), 250);
+    let input = aoc_utils::full_input!(18);
+    assert_eq!(part1(&input, 1024, 70), 250);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT, 1024, 70), (56, 8));
+    let input = aoc_utils::full_input!(18);
+    assert_eq!(part2(&input, 1024, 70), (56, 8));
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("5,4\n4;2\n", 2, 6).unwrap_err().to_string(), "line 2: expected a point like 5,4, found \"4;2\"");
+    assert_eq!(try_part1("5,4\n4,7\n", 2, 6).unwrap_err().to_string(), "line 2: 4,7 is outside the memory space");
+    assert_eq!(try_part2("5,4\n4,2\n", 1, 6), Err(AocError::new("the path is never blocked")));
+    assert_eq!(try_part2("0,1\n1,0\n2,2\n", 1, 6), Ok((1, 0)));
 }
@@ -1,11 +1,12 @@
+use aoc_utils::load_input_or_exit;
 use day18::{part1, part2};
 
 fn main() {
-    let input = include_str!("../input.txt");
+    let input = load_input_or_exit(18);
 
-    let result1 = part1(input, 1024, 70);
+    let result1 = part1(&input, 1024, 70);
     println!("Part 1: {result1}");
 
-    let (x,y) = part2(input, 1024, 70);
+    let (x,y) = part2(&input, 1024, 70);
     println!("Part 2: {x},{y}");
 }
@@ -1,22 +1,29 @@
+use std::sync::LazyLock;
+
+use aoc_utils::{input_path, load_input};
 use day19::{ part1, part2_memoize, part2_dynamic };
 
 fn main() {
+    if let Err(err) = input_path(19) {
+        eprintln!("Skipping benchmarks: {err}");
+        return;
+    }
     divan::main();
 }
 
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(|| load_input(19).expect("input"));
 
 #[divan::bench]
 fn bench_part1() {
-    part1(INPUT);
+    part1(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2_dynamic() {
-    part2_dynamic(INPUT);
+    part2_dynamic(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2_memoize() {
-    part2_memoize(INPUT);
+    part2_memoize(&INPUT);
 }
@@ -1,6 +1,22 @@
 use std::collections::HashMap;
 
 use nom::{bytes::tag, character::complete::alpha1, multi::separated_list1, sequence::separated_pair, Parser, IResult};
+use aoc_utils::{AocError, NomResultExt, Solution, Variant};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("memoize", try_part2),
+        Variant::new("dynamic", part2_dynamic),
+    ];
+}
 
 fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
     let (remaining, (towels, patterns))= separated_pair(
@@ -12,13 +28,32 @@ fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
     Ok((remaining, (towels, patterns)))
 }
 
+fn parse_towels(input: &str) -> Result<(Vec<&str>, Vec<&str>), AocError> {
+    let (remaining, (towels, patterns)) = parse_input(input).located_in(input)?;
+    if !remaining.trim_end().is_empty() {
+        let remaining = remaining.trim_start_matches('\n');
+        return Err(AocError::at(input, remaining, "expected a pattern of towel colors"));
+    }
+    Ok((towels, patterns))
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    parse_towels(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    parse_towels(input)?;
+    Ok(part2(input))
+}
+
 pub fn part1(input: &str) -> usize {
     fn pattern_from_towels(pattern: &str, towels: &[&str]) -> bool {
         if pattern.is_empty() { return true }
         towels.iter().any(|t| pattern.starts_with(t) && pattern_from_towels(&pattern[t.len()..], towels))
     }
 
-    let (_remaining, (towels, patterns)) = parse_input(input).expect("parse");
+    let (towels, patterns) = parse_towels(input).unwrap_or_else(|err| panic!("{err}"));
     patterns.iter().filter(|p| pattern_from_towels(p, &towels)).count()
 }
 
@@ -28,7 +63,7 @@ pub fn part1(input: &str) -> usize {
 // Let's try a dynamic programming approach.
 //
 pub fn part2_dynamic(input: &str) -> usize {
-    let (_remaining, (towels, patterns)) = parse_input(input).expect("parse");
+    let (towels, patterns) = parse_towels(input).unwrap_or_else(|err| panic!("{err}"));
     patterns.iter().map(|p| {
         let mut cache = HashMap::<&str,usize>::new();
         for i in (0..p.len()).rev() {
@@ -69,7 +104,7 @@ pub fn part2_memoize(input: &str) -> usize {
         v
     }
 
-    let (_remaining, (towels, patterns)) = parse_input(input).expect("parse");
+    let (towels, patterns) = parse_towels(input).unwrap_or_else(|err| panic!("{err}"));
     let mut cache = HashMap::default();
     patterns.iter().map(|p| pattern_from_towels(p, &towels, &mut cache)).sum()
 }
@@ -128,15 +163,45 @@ gbr
     assert_eq!(part2(input), 3);
 }
 
-#[cfg(test)]
-const FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 238);
+    let input = aoc_utils::full_input!(19);
+    assert_eq!(part1(&input), 238);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 635018909726691);
+    let input = aoc_utils::full_input!(19);
+    assert_eq!(part2(&input), 635018909726691);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("r, wr, b\nbrwrr\n").unwrap_err().to_string(), "line 1, column 9: unexpected input (nom Tag)");
+    assert_eq!(try_part2("r, wr, b\n\nbrwrr\nbg-gr\n").unwrap_err().to_string(),
+        "line 4, column 3: expected a pattern of towel colors");
+    assert_eq!(try_part2("r, wr, b\n\nbrwrr\n"), Ok(1));
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+    aoc_utils::assert_variants_agree::<Day19>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(19);
+    aoc_utils::assert_variants_agree::<Day19>(&input);
 }
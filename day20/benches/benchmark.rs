@@ -1,22 +1,29 @@
+use std::sync::LazyLock;
+
+use aoc_utils::{input_path, load_input};
 use day20::{ part1, part2_limit_v1, part2_limit_v2 };
 
 fn main() {
+    if let Err(err) = input_path(20) {
+        eprintln!("Skipping benchmarks: {err}");
+        return;
+    }
     divan::main();
 }
 
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(|| load_input(20).expect("input"));
 
 #[divan::bench]
 fn bench_part1() {
-    part1(INPUT);
+    part1(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2_limit() {
-    part2_limit_v1(INPUT, 100);
+    part2_limit_v1(&INPUT, 100);
 }
 
 #[divan::bench]
 fn bench_part2_v2() {
-    part2_limit_v2(INPUT, 100);
+    part2_limit_v2(&INPUT, 100);
 }
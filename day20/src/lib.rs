@@ -1,9 +1,65 @@
 use rustc_hash::{FxHashMap, FxHashSet};
+use aoc_utils::{AocError, Grid, Solution, Variant};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<usize>] = &[
+        Variant::fallible("v2", try_part2),
+        Variant::new("v1", |input| part2_limit_v1(input, 100)),
+    ];
+}
 
 type Row = isize;
 type Col = isize;
 type Coord = (Row, Col);
 
+//
+// The racetrack must be a single path from S to E, with no branches,
+// surrounded by walls.
+//
+fn check_track(input: &str) -> Result<(), AocError> {
+    let map = Grid::parse_checked(input, |ch| ".#SE".contains(ch))?;
+    let last_row = map.rows() - 1;
+    let last_col = map.cols() - 1;
+    for (row, col) in map.positions() {
+        if (row == 0 || col == 0 || row == last_row || col == last_col) && map[(row, col)] != b'#' {
+            return Err(AocError::at_cell((row, col), "the racetrack must be surrounded by walls"));
+        }
+    }
+    for (name, ch) in [("start", b'S'), ("end", b'E')] {
+        match map.find_all(&ch).count() {
+            0 => return Err(AocError::new(format!("no {name}?"))),
+            1 => {}
+            n => return Err(AocError::new(format!("expected one {name}, found {n}"))),
+        }
+    }
+    for pos in map.positions().filter(|&pos| map[pos] != b'#') {
+        let neighbors = map.neighbors4(pos).filter(|&n| map[n] != b'#').count();
+        let expected = if map[pos] == b'.' { 2 } else { 1 };
+        if neighbors != expected {
+            return Err(AocError::at_cell(pos, "the racetrack must be a single path from S to E"));
+        }
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_track(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<usize, AocError> {
+    check_track(input)?;
+    Ok(part2(input))
+}
+
 //
 // Oooh!  This is an interesting one!
 //
@@ -98,7 +154,7 @@ pub fn part2_limit_v2(input: &str, limit: usize) -> usize {
     // path that have indices at least `limit+1` apart.  Then filter
     // out ones where the Manhattan distance is too large, or the
     // offset minus Manhattan distance is too small.
-    for (start_index, start) in path[..path.len()-limit].iter().enumerate() {
+    for (start_index, start) in path[..path.len().saturating_sub(limit)].iter().enumerate() {
         for (end_index, end) in path[start_index+limit..].iter().enumerate() {
             let dist = ((start.0 - end.0).abs() + (start.1 - end.1).abs()) as usize;
             if dist <= 20 && end_index >= dist {
@@ -236,20 +292,57 @@ fn test_part2() {
     assert_eq!(part2_limit_v2(input, 71), 22+4+3);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 1485);
+    let input = aoc_utils::full_input!(20);
+    assert_eq!(part1(&input), 1485);
 }
 
 #[test]
 fn test_part2_v1_full() {
-    assert_eq!(part2_limit_v1(FULL_INPUT, 100), 1027501);
+    let input = aoc_utils::full_input!(20);
+    assert_eq!(part2_limit_v1(&input, 100), 1027501);
 }
 
 #[test]
 fn test_part2_v2_full() {
-    assert_eq!(part2_limit_v2(FULL_INPUT, 100), 1027501);
+    let input = aoc_utils::full_input!(20);
+    assert_eq!(part2_limit_v2(&input, 100), 1027501);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("#####\n#S.E#\n#####\n"), Ok(0));
+    assert_eq!(try_part2("#####\n#S..#\n#####\n"), Err(AocError::new("no end?")));
+    assert_eq!(try_part1("#####\n#S.E.\n#####\n").unwrap_err().to_string(), "line 2, column 5: the racetrack must be surrounded by walls");
+    assert_eq!(try_part2("#####\n#S.E#\n#.###\n#####\n").unwrap_err().to_string(),
+        "line 2, column 2: the racetrack must be a single path from S to E");
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+    aoc_utils::assert_variants_agree::<Day20>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(20);
+    aoc_utils::assert_variants_agree::<Day20>(&input);
 }
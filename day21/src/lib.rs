@@ -1,5 +1,20 @@
 use std::{collections::HashMap, sync::LazyLock};
 use itertools::Itertools;
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    type Answer1 = usize;
+    type Answer2 = u64;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
 
 //
 // There are three robots here.  Number one is typing on the numeric
@@ -77,6 +92,30 @@ use itertools::Itertools;
 // +---+---+---+
 //
 
+//
+// Each code is a number typed on the numeric keypad, followed by A.
+//
+fn check_codes(input: &str) -> Result<(), AocError> {
+    for (i, line) in input.lines().enumerate() {
+        let digits = line.strip_suffix('A')
+            .ok_or_else(|| AocError::at_line(i + 1, format!("code {line:?} should end with A")))?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(AocError::at_line(i + 1, format!("code {line:?} should be digits followed by A")));
+        }
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_codes(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<u64, AocError> {
+    check_codes(input)?;
+    Ok(part2(input))
+}
+
 pub fn part1(input: &str) -> usize {
     input.lines().map(|line| {
         let code = line.strip_suffix('A').unwrap().parse::<usize>().unwrap();
@@ -219,7 +258,7 @@ fn num_presses_for_code(
     }
 
     if depth == 0 {
-        assert_eq!(numeric, false);
+        assert!(!numeric);
         let result = ways_for_keys(src, dest, numeric).into_iter()
             .map(|s| s.len())
             .min()
@@ -504,16 +543,21 @@ fn test_part2_others() {
     assert_eq!(part2_inner("379A", 25), 379 * 77985628636);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 94284);
+    let input = aoc_utils::full_input!(21);
+    assert_eq!(part1(&input), 94284);
 }
 
 #[test]
 fn test_part2_full() {
-    let result = part2(FULL_INPUT);
+    let input = aoc_utils::full_input!(21);
+    let result = part2(&input);
     assert_eq!(result, 116_821_732_384_052);
 }
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("029A\n980\n").unwrap_err().to_string(), "line 2: code \"980\" should end with A");
+    assert_eq!(try_part2("029A\n9<0A\n").unwrap_err().to_string(), "line 2: code \"9<0A\" should be digits followed by A");
+}
@@ -0,0 +1,26 @@
+//
+// Every buyer's secret number goes through the same 2000 steps, so the
+// time should grow in proportion to the number of buyers.
+//
+use aoc_utils::bench::generated;
+use divan::Bencher;
+use day22::{ part1, part2 };
+
+fn main() {
+    divan::main();
+}
+
+// Buyers (the real input has about 2000)
+const SIZES: &[usize] = &[250, 500, 1000, 2000, 4000];
+
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, size: usize) {
+    let input = generated(22, size);
+    bencher.bench(|| part1(&input));
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_part2(bencher: Bencher, size: usize) {
+    let input = generated(22, size);
+    bencher.bench(|| part2(&input));
+}
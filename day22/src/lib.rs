@@ -1,6 +1,44 @@
 use std::collections::{HashMap,HashSet};
 use rustc_hash::FxBuildHasher;
 use itertools::Itertools;
+use aoc_utils::{AocError, LineContext, Solution, Variant};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+    type Answer1 = u64;
+    type Answer2 = u32;
+    const PART1: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<u32>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+//
+// One buyer's initial secret number per line.
+//
+fn check_buyers(input: &str) -> Result<(), AocError> {
+    if input.trim().is_empty() {
+        return Err(AocError::new("no buyers"));
+    }
+    for (i, line) in input.lines().enumerate() {
+        line.parse::<u64>().on_line(i + 1)?;
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<u64, AocError> {
+    check_buyers(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<u32, AocError> {
+    check_buyers(input)?;
+    Ok(part2(input))
+}
 
 pub fn part1(input: &str) -> u64 {
     input.lines()
@@ -109,15 +147,20 @@ fn test_part2() {
     assert_eq!(part2(input), 23);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 14392541715);
+    let input = aoc_utils::full_input!(22);
+    assert_eq!(part1(&input), 14392541715);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), 1628);
+    let input = aoc_utils::full_input!(22);
+    assert_eq!(part2(&input), 1628);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1(""), Err(AocError::new("no buyers")));
+    assert_eq!(try_part2("1\n10\n-100\n").unwrap_err().to_string(), "line 3: invalid number: invalid digit found in string");
 }
@@ -1,27 +1,34 @@
+use std::sync::LazyLock;
+
+use aoc_utils::{input_path, load_input};
 use day23::{ part1, part2_orig, part2_incremental, part2_greedy };
 
 fn main() {
+    if let Err(err) = input_path(23) {
+        eprintln!("Skipping benchmarks: {err}");
+        return;
+    }
     divan::main();
 }
 
-const INPUT: &str = include_str!("../input.txt");
+static INPUT: LazyLock<String> = LazyLock::new(|| load_input(23).expect("input"));
 
 #[divan::bench]
 fn bench_part1() {
-    part1(INPUT);
+    part1(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2_orig() {
-    part2_orig(INPUT);
+    part2_orig(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2_incremental() {
-    part2_incremental(INPUT);
+    part2_incremental(&INPUT);
 }
 
 #[divan::bench]
 fn bench_part2_greedy() {
-    part2_greedy(INPUT);
+    part2_greedy(&INPUT);
 }
@@ -0,0 +1,38 @@
+//
+// Networks of increasing size.  part2_orig builds every clique of each
+// size in turn, which is what makes it blow up on bigger networks.
+//
+use aoc_utils::bench::generated;
+use divan::Bencher;
+use day23::{ part1, part2_orig, part2_incremental, part2_greedy };
+
+fn main() {
+    divan::main();
+}
+
+// Computers, in groups of 13 (the real input has 520)
+const SIZES: &[usize] = &[78, 156, 312, 520, 676];
+
+#[divan::bench(args = SIZES)]
+fn bench_part1(bencher: Bencher, size: usize) {
+    let input = generated(23, size);
+    bencher.bench(|| part1(&input));
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_part2_orig(bencher: Bencher, size: usize) {
+    let input = generated(23, size);
+    bencher.bench(|| part2_orig(&input));
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_part2_incremental(bencher: Bencher, size: usize) {
+    let input = generated(23, size);
+    bencher.bench(|| part2_incremental(&input));
+}
+
+#[divan::bench(args = SIZES)]
+fn bench_part2_greedy(bencher: Bencher, size: usize) {
+    let input = generated(23, size);
+    bencher.bench(|| part2_greedy(&input));
+}
@@ -2,6 +2,51 @@ use std::collections::{HashMap, HashSet};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 pub use part2_greedy as part2;
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Answer1 = usize;
+    type Answer2 = String;
+    const PART1: &'static [Variant<usize>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<String>] = &[
+        Variant::fallible("greedy", try_part2),
+        Variant::new("orig", part2_orig),
+        Variant::new("incremental", part2_incremental),
+    ];
+}
+
+//
+// Each line is a connection between two computers, like "kh-tc".
+//
+fn check_connections(input: &str) -> Result<(), AocError> {
+    if input.trim().is_empty() {
+        return Err(AocError::new("no connections"));
+    }
+    for (i, line) in input.lines().enumerate() {
+        let valid = line.split_once('-').is_some_and(|(left, right)| {
+            [left, right].iter().all(|name| !name.is_empty() && name.bytes().all(|b| b.is_ascii_alphanumeric()))
+        });
+        if !valid {
+            return Err(AocError::at_line(i + 1, format!("expected a connection like kh-tc, found {line:?}")));
+        }
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<usize, AocError> {
+    check_connections(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<String, AocError> {
+    check_connections(input)?;
+    Ok(part2(input))
+}
 
 pub fn part1(input: &str) -> usize {
     let mut connections: FxHashMap<&str, FxHashSet<&str>> = FxHashMap::default();
@@ -319,25 +364,78 @@ td-yn
     assert_eq!(part2_greedy(input), "co,de,ka,ta");
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 1370);
+    let input = aoc_utils::full_input!(23);
+    assert_eq!(part1(&input), 1370);
 }
 
 #[test]
 fn test_part2_orig_full() {
-    assert_eq!(part2_orig(FULL_INPUT), "am,au,be,cm,fo,ha,hh,im,nt,os,qz,rr,so");
+    let input = aoc_utils::full_input!(23);
+    assert_eq!(part2_orig(&input), "am,au,be,cm,fo,ha,hh,im,nt,os,qz,rr,so");
 }
 
 #[test]
 fn test_part2_incremental_full() {
-    assert_eq!(part2_incremental(FULL_INPUT), "am,au,be,cm,fo,ha,hh,im,nt,os,qz,rr,so");
+    let input = aoc_utils::full_input!(23);
+    assert_eq!(part2_incremental(&input), "am,au,be,cm,fo,ha,hh,im,nt,os,qz,rr,so");
 }
 
 #[test]
 fn test_part2_greedy_full() {
-    assert_eq!(part2_greedy(FULL_INPUT), "am,au,be,cm,fo,ha,hh,im,nt,os,qz,rr,so");
+    let input = aoc_utils::full_input!(23);
+    assert_eq!(part2_greedy(&input), "am,au,be,cm,fo,ha,hh,im,nt,os,qz,rr,so");
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(try_part1("\n"), Err(AocError::new("no connections")));
+    assert_eq!(try_part2("kh-tc\nqp kh\n").unwrap_err().to_string(), "line 2: expected a connection like kh-tc, found \"qp kh\"");
+    assert_eq!(try_part2("kh-tc\nqp-\n").unwrap_err().line, Some(2));
+}
+
+#[test]
+fn test_variants_agree() {
+    let input = "\
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+    aoc_utils::assert_variants_agree::<Day23>(input);
+}
+
+#[test]
+fn test_variants_agree_full() {
+    let input = aoc_utils::full_input!(23);
+    aoc_utils::assert_variants_agree::<Day23>(&input);
 }
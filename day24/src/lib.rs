@@ -8,6 +8,21 @@ use nom::{
     sequence::{delimited, preceded, separated_pair, terminated},
     IResult, Parser
 };
+use aoc_utils::{AocError, NomResultExt, Solution, Variant};
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    type Answer1 = u64;
+    type Answer2 = String;
+    const PART1: &'static [Variant<u64>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<String>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
 
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -44,11 +59,16 @@ impl Gate<'_> {
 }
 
 pub fn part1(input: &str) -> u64 {
+    try_part1(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+pub fn try_part1(input: &str) -> Result<u64, AocError> {
     // Parse the input
-    let (_, (mut wires, mut gates)) = parse_input(input).expect("valid input");
+    let (_, (mut wires, mut gates)) = parse_input(input).located_in(input)?;
 
     // Simulate all of the gates
     while !gates.is_empty() {
+        let remaining = gates.len();
         gates.retain(|gate| {
             if let Some(in0) = wires.get(gate.inputs[0]) {
                 if let Some(in1) = wires.get(gate.inputs[1]) {
@@ -59,6 +79,10 @@ pub fn part1(input: &str) -> u64 {
             }
             true
         });
+        if gates.len() == remaining {
+            return Err(AocError::new(format!("the inputs of gate {} -> {} are never set",
+                gates[0].inputs.join(" and "), gates[0].output)));
+        }
     }
 
     // Construct the output value
@@ -68,7 +92,7 @@ pub fn part1(input: &str) -> u64 {
         let value = wires.get(&wire_name[..]).unwrap_or(&false);
         result = result * 2 + if *value { 1 } else { 0 };
     }
-    result
+    Ok(result)
 }
 
 //
@@ -130,70 +154,87 @@ pub fn part1(input: &str) -> u64 {
 // output is correct.
 //
 pub fn part2(input: &str) -> String {
+    try_part2(input).unwrap_or_else(|err| panic!("{err}"))
+}
+
+//
+// Any part of the circuit that doesn't look like the adder described
+// above (beyond the crossed wires) is reported as an error.
+//
+fn check_adder(ok: bool, bit: u32, what: &str) -> Result<(), AocError> {
+    match ok {
+        true => Ok(()),
+        false => Err(AocError::new(format!("bit {bit}: {what}; is this a ripple-carry adder?"))),
+    }
+}
+
+pub fn try_part2(input: &str) -> Result<String, AocError> {
     // From manual inspection, bits 0..=8 are fine, and we have one swap
     // involving bit 9: "hnd" and "z09".
     // Note the z09 output was an AND, not an XOR
     let mut crossed_wires: Vec<String> = vec![];
 
-    let (_input, (_wires, mut gates)) = parse_input(input).expect("valid input");
-    let gate = find_gate("x00", Operation::XOR, &gates);
+    let (_input, (_wires, mut gates)) = parse_input(input).located_in(input)?;
+    let gate = find_gate("x00", Operation::XOR, &gates)?;
     if gate.output != "z00" {
-        println!("Swap: z00, {}", gate.output);
-        return "TODO".to_string();
+        return Err(AocError::new(format!("z00 is crossed with {}, which isn't handled", gate.output)));
     }
-    let mut carry = find_gate("x00", Operation::AND, &gates).output;
+    let mut carry = find_gate("x00", Operation::AND, &gates)?.output;
 
     for bit in 1..=44 {
         // eprintln!("Bit {bit}...");
         let x_str = format!("x{bit:02}");
         let z_str = format!("z{bit:02}");
         
-        let mut znn = *gates.iter().find(|g| g.output==z_str).unwrap();
+        let mut znn = *gates.iter().find(|g| g.output==z_str)
+            .ok_or_else(|| AocError::new(format!("no gate outputs {z_str}")))?;
         if znn.operation != Operation::XOR {
-            let mut other = find_gate(carry, Operation::XOR, &gates);
+            let mut other = find_gate(carry, Operation::XOR, &gates)?;
             let other_out = other.output;
             // eprintln!("{z_str} <-> {}", other_out);
-            swap_outputs(&z_str, other_out, &mut gates);
+            swap_outputs(&z_str, other_out, &mut gates)?;
             crossed_wires.push(z_str);
             crossed_wires.push(other_out.to_string());
             other.output = znn.output;
             znn = other;
         }
-        assert!(znn.inputs.contains(&carry));
-        let mut ddd = find_gate(&x_str, Operation::XOR, &gates);
-        let mut eee = find_gate(&x_str, Operation::AND, &gates);
+        check_adder(znn.inputs.contains(&carry), bit, "the sum doesn't use the carry in")?;
+        let mut ddd = find_gate(&x_str, Operation::XOR, &gates)?;
+        let mut eee = find_gate(&x_str, Operation::AND, &gates)?;
         if !znn.inputs.contains(&ddd.output) && znn.inputs.contains(&eee.output) {
             // eprintln!("{} <-> {}", ddd.output, eee.output);
             crossed_wires.push(ddd.output.to_string());
             crossed_wires.push(eee.output.to_string());
-            swap_outputs(ddd.output, eee.output, &mut gates);
+            swap_outputs(ddd.output, eee.output, &mut gates)?;
             swap(&mut ddd.output, &mut eee.output);
         }
-        assert!(znn.inputs.contains(&ddd.output));
-        let fff = find_gate(carry, Operation::AND, &gates);
-        assert!(fff.inputs.contains(&ddd.output));
-        let ggg = find_gate(fff.output, Operation::OR, &gates);
-        assert!(ggg.inputs.contains(&eee.output));
+        check_adder(znn.inputs.contains(&ddd.output), bit, "the sum doesn't use x XOR y")?;
+        let fff = find_gate(carry, Operation::AND, &gates)?;
+        check_adder(fff.inputs.contains(&ddd.output), bit, "the carry doesn't use x XOR y")?;
+        let ggg = find_gate(fff.output, Operation::OR, &gates)?;
+        check_adder(ggg.inputs.contains(&eee.output), bit, "the carry out doesn't use x AND y")?;
 
         carry = ggg.output;
     }
-    assert_eq!(carry, "z45");
-    assert_eq!(crossed_wires.len(), 8);
+    check_adder(carry == "z45", 44, "the last carry out isn't z45")?;
+    if crossed_wires.len() != 8 {
+        return Err(AocError::new(format!("expected 4 pairs of crossed wires, found {}", crossed_wires.len() / 2)));
+    }
 
     crossed_wires.sort();
-    crossed_wires.join(",")
+    Ok(crossed_wires.join(","))
 }
 
-fn find_gate<'a>(src: &str, operation: Operation, gates: &[Gate<'a>]) -> Gate<'a> {
+fn find_gate<'a>(src: &str, operation: Operation, gates: &[Gate<'a>]) -> Result<Gate<'a>, AocError> {
     for gate in gates {
         if gate.operation == operation && (gate.inputs.contains(&src)) {
-            return *gate;
+            return Ok(*gate);
         }
     }
-    panic!("gate not found!");
+    Err(AocError::new(format!("no {operation:?} gate with input {src}")))
 }
 
-fn swap_outputs(wire1: &str, wire2: &str, gates: &mut [Gate]) {
+fn swap_outputs(wire1: &str, wire2: &str, gates: &mut [Gate]) -> Result<(), AocError> {
     let mut gate1 = None;
     let mut gate2 = None;
     for gate in gates.iter_mut() {
@@ -203,9 +244,10 @@ fn swap_outputs(wire1: &str, wire2: &str, gates: &mut [Gate]) {
             gate2 = Some(gate);
         }
     }
-    let gate1 = gate1.unwrap();
-    let gate2 = gate2.unwrap();
+    let gate1 = gate1.ok_or_else(|| AocError::new(format!("no gate outputs {wire1}")))?;
+    let gate2 = gate2.ok_or_else(|| AocError::new(format!("no gate outputs {wire2}")))?;
     swap(&mut gate1.output, &mut gate2.output);
+    Ok(())
 }
 
 fn parse_wire(input: &str) -> IResult<&str, (&str, bool)> {
@@ -222,7 +264,7 @@ fn parse_op(input: &str) -> IResult<&str, Operation> {
     )).parse(input)
 }
 
-fn parse_gate(input: &str) -> IResult<&str, Gate> {
+fn parse_gate(input: &str) -> IResult<&str, Gate<'_>> {
     let (input, (src1, operation, src2, output)) = (
         alphanumeric1,
         delimited(tag(" "), parse_op, tag(" ")),
@@ -233,7 +275,7 @@ fn parse_gate(input: &str) -> IResult<&str, Gate> {
     Ok((input, Gate{operation, inputs, output}))
 }
 
-fn parse_input(input: &str) -> IResult<&str, (HashMap<&str, bool>, Vec<Gate>)> {
+fn parse_input(input: &str) -> IResult<&str, (HashMap<&str, bool>, Vec<Gate<'_>>)> {
     let (input, (wires, gates)) = all_consuming(
         separated_pair(
             many1(terminated(parse_wire, newline)),
@@ -315,15 +357,24 @@ tnw OR pbm -> gnj
     assert_eq!(part1(input), 2024);
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 53190357879014);
+    let input = aoc_utils::full_input!(24);
+    assert_eq!(part1(&input), 53190357879014);
 }
 
 #[test]
 fn test_part2_full() {
-    assert_eq!(part2(FULL_INPUT), "bks,hnd,nrn,tdv,tjp,z09,z16,z23");
+    let input = aoc_utils::full_input!(24);
+    assert_eq!(part2(&input), "bks,hnd,nrn,tdv,tjp,z09,z16,z23");
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = try_part1("x00: 1\ny00: 0\n\nx00 AND y00 -> z00\nx00 NAND y00 -> z01\n").unwrap_err();
+    assert_eq!((err.line, err.column), (Some(5), Some(1)));
+    let err = try_part1("x00: 1\ny00: 0\n\nx00 AND y01 -> z00\n").unwrap_err();
+    assert_eq!(err, AocError::new("the inputs of gate x00 and y01 -> z00 are never set"));
+    let err = try_part2("x00: 1\ny00: 0\n\nx00 AND y00 -> z00\n").unwrap_err();
+    assert_eq!(err, AocError::new("no XOR gate with input x00"));
 }
@@ -1,3 +1,50 @@
+use aoc_utils::{AocError, Solution, Variant};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    type Answer1 = u32;
+    type Answer2 = String;
+    const PART1: &'static [Variant<u32>] = &[
+        Variant::fallible("default", try_part1),
+    ];
+    const PART2: &'static [Variant<String>] = &[
+        Variant::fallible("default", try_part2),
+    ];
+}
+
+//
+// The bitmap trick below depends on every lock and key being exactly
+// 5 columns by 7 rows of '#' and '.', separated by blank lines.
+//
+fn check_schematics(input: &str) -> Result<(), AocError> {
+    let mut line_number = 1;
+    for schematic in input.split("\n\n") {
+        let lines: Vec<&str> = schematic.lines().collect();
+        if lines.len() != 7 {
+            return Err(AocError::at_line(line_number, format!("expected 7 rows in schematic, found {}", lines.len())));
+        }
+        for (i, line) in lines.iter().enumerate() {
+            if line.len() != 5 || !line.bytes().all(|b| b == b'#' || b == b'.') {
+                return Err(AocError::at_line(line_number + i, format!("expected 5 of '#' or '.', found {line:?}")));
+            }
+        }
+        line_number += 8;
+    }
+    Ok(())
+}
+
+pub fn try_part1(input: &str) -> Result<u32, AocError> {
+    check_schematics(input)?;
+    Ok(part1(input))
+}
+
+pub fn try_part2(input: &str) -> Result<String, AocError> {
+    check_schematics(input)?;
+    Ok(part2(input))
+}
+
 //
 // The goal is to count how many unique pairs of lock and key do not
 // overlap.
@@ -91,10 +138,16 @@ fn test_part2() {
     assert_eq!(part2(input), "World");
 }
 
-#[cfg(test)]
-static FULL_INPUT: &str = include_str!("../input.txt");
-
 #[test]
 fn test_part1_full() {
-    assert_eq!(part1(FULL_INPUT), 3107);
+    let input = aoc_utils::full_input!(25);
+    assert_eq!(part1(&input), 3107);
+}
+
+#[test]
+fn test_invalid_input() {
+    let err = try_part1("#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....\n\n#####\n##.##\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 9: expected 7 rows in schematic, found 2");
+    let err = try_part2("#####\n.####\n.####\n.###\n.#.#.\n.#...\n.....\n").unwrap_err();
+    assert_eq!(err.to_string(), "line 4: expected 5 of '#' or '.', found \".###\"");
 }